@@ -0,0 +1,125 @@
+use crate::{InputSource, Puzzle, Solution};
+use serde::{Serialize, Serializer};
+use std::{
+	fmt::{self, Display, Formatter},
+	time::{Duration, Instant}
+};
+
+fn serialize_secs<S: Serializer>(
+	duration: &Duration,
+	serializer: S
+) -> Result<S::Ok, S::Error> {
+	serializer.serialize_f64(duration.as_secs_f64())
+}
+
+/// Statistics about the time one step of a solution took over several runs. All times are
+/// serialized as seconds.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Stats {
+	#[serde(serialize_with = "serialize_secs")]
+	pub mean: Duration,
+	#[serde(serialize_with = "serialize_secs")]
+	pub min: Duration,
+	#[serde(serialize_with = "serialize_secs")]
+	pub max: Duration
+}
+
+impl Stats {
+	fn new(times: &[Duration]) -> Self {
+		Self {
+			mean: times.iter().sum::<Duration>() / times.len() as u32,
+			min: times.iter().copied().min().unwrap(),
+			max: times.iter().copied().max().unwrap()
+		}
+	}
+}
+
+impl Display for Stats {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"mean {:>10.3?}  min {:>10.3?}  max {:>10.3?}",
+			self.mean, self.min, self.max
+		)
+	}
+}
+
+/// The time the solution of one day took.
+#[derive(Clone, Debug, Serialize)]
+pub struct Bench {
+	pub day: u8,
+	pub puzzle: &'static str,
+	pub runs: usize,
+	pub parse: Stats,
+	/// The time part 1 took, unless it was skipped.
+	pub part1: Option<Stats>,
+	/// The time part 2 took, unless it was skipped.
+	pub part2: Option<Stats>
+}
+
+impl Display for Bench {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		writeln!(f, "Day {} ({} runs)", self.day, self.runs)?;
+		writeln!(f, "  parse:  {}", self.parse)?;
+		if let Some(part1) = &self.part1 {
+			writeln!(f, "  part 1: {part1}")?;
+		}
+		if let Some(part2) = &self.part2 {
+			writeln!(f, "  part 2: {part2}")?;
+		}
+		Ok(())
+	}
+}
+
+fn time<T>(f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<(T, Duration)> {
+	let start = Instant::now();
+	let value = f()?;
+	Ok((value, start.elapsed()))
+}
+
+pub(crate) fn bench_solution<S: Solution>(
+	day: u8,
+	part: Option<u8>,
+	runs: usize,
+	input: &InputSource,
+	puzzle: Puzzle
+) -> anyhow::Result<Bench> {
+	assert!(runs > 0, "Need at least one run");
+
+	// stdin can only be read once
+	let stdin;
+	let input = match input {
+		InputSource::Stdin => {
+			stdin = InputSource::Text(input.read_to_string()?);
+			&stdin
+		},
+		input => input
+	};
+
+	let params = puzzle.params::<S>();
+	let mut parse = Vec::with_capacity(runs);
+	let mut part1 = Vec::with_capacity(runs);
+	let mut part2 = Vec::with_capacity(runs);
+	for _ in 0 .. runs {
+		let (input, t) = time(|| S::parse(input))?;
+		parse.push(t);
+		if part != Some(2) {
+			part1.push(time(|| S::part1(&input, &params))?.1);
+		}
+		if part != Some(1) {
+			part2.push(time(|| S::part2(&input, &params))?.1);
+		}
+	}
+
+	Ok(Bench {
+		day,
+		puzzle: match puzzle {
+			Puzzle::Example => "example",
+			Puzzle::Real => "real"
+		},
+		runs,
+		parse: Stats::new(&parse),
+		part1: (!part1.is_empty()).then(|| Stats::new(&part1)),
+		part2: (!part2.is_empty()).then(|| Stats::new(&part2))
+	})
+}
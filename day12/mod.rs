@@ -0,0 +1,104 @@
+#![allow(clippy::enum_variant_names)]
+#![warn(rust_2018_idioms, unreachable_pub)]
+#![forbid(elided_lifetimes_in_paths, unsafe_code)]
+
+use crate::{lines, read, Answer, Grid, InputSource, Point, Solution};
+use anyhow::bail;
+use chumsky::prelude::*;
+use log::{debug, trace};
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Height {
+	Height(u32),
+	Start,
+	End
+}
+
+impl Height {
+	fn height(self) -> u32 {
+		match self {
+			Self::Height(height) => height,
+			Self::Start => 0,
+			Self::End => 25
+		}
+	}
+}
+
+fn parser() -> impl Parser<char, Grid<Height>, Error = Simple<char>> {
+	let height = choice((
+		just('S').to(Height::Start),
+		just('E').to(Height::End),
+		filter(char::is_ascii_lowercase).map(|ch| Height::Height(ch as u32 - 'a' as u32))
+	));
+	lines(height.repeated().at_least(1))
+		.then_ignore(end())
+		.map(Grid::from_rows)
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct Path {
+	pos: Point,
+	steps: usize
+}
+
+/// Find the shortest path from the end to the start. If `any_start` is true, any
+/// square at the lowest elevation is accepted as the start.
+fn bfs(heightmap: &Grid<Height>, any_start: bool) -> anyhow::Result<usize> {
+	let mut visited = Grid::sparse();
+	let mut q = VecDeque::new();
+	for (pos, height) in heightmap.iter() {
+		if *height == Height::End {
+			q.push_front(Path { pos, steps: 0 });
+			visited.insert(pos, ());
+		}
+	}
+	while let Some(path) = q.pop_front() {
+		debug!("path={path:?}");
+		let height = heightmap[path.pos].height();
+		for (pos, next) in heightmap.neighbours4(path.pos) {
+			if visited.contains(pos) || next.height() + 1 < height {
+				continue;
+			}
+			trace!(" -> {pos}?");
+			match next {
+				Height::Start => return Ok(path.steps + 1),
+				next if any_start && next.height() == 0 => {
+					return Ok(path.steps + 1);
+				},
+				Height::Height(_) => {
+					q.push_back(Path {
+						pos,
+						steps: path.steps + 1
+					});
+					visited.insert(pos, ());
+				},
+				_ => {}
+			}
+		}
+	}
+
+	bail!("There is no path from the end to the start")
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+	type Input = Grid<Height>;
+	type Params = ();
+
+	const EXAMPLE: () = ();
+	const REAL: () = ();
+
+	fn parse(input: &InputSource) -> anyhow::Result<Grid<Height>> {
+		read(input, parser())
+	}
+
+	fn part1(heightmap: &Grid<Height>, _: &()) -> anyhow::Result<Answer> {
+		Ok(bfs(heightmap, false)?.into())
+	}
+
+	fn part2(heightmap: &Grid<Height>, _: &()) -> anyhow::Result<Answer> {
+		Ok(bfs(heightmap, true)?.into())
+	}
+}
@@ -1,4 +1,4 @@
-use aoc22::read;
+use crate::{read, Answer, InputSource, Solution};
 use chumsky::{prelude::*, text::digits};
 use std::{cmp::Ordering, collections::BTreeSet};
 
@@ -27,9 +27,18 @@ impl Value {
 	}
 }
 
+/// Parse the pairs of packets. If a packet fails to parse, the error is recorded and its
+/// pair is skipped.
 fn parser() -> impl Parser<char, Vec<(Value, Value)>, Error = Simple<char>> {
-	let value = Value::parser();
-	let pair = value.clone().then_ignore(just("\n")).then(value);
+	let value =
+		Value::parser()
+			.map(Some)
+			.recover_with(nested_delimiters('[', ']', [], |_| None));
+	let pair = value
+		.clone()
+		.then_ignore(just("\n"))
+		.then(value)
+		.map(|(lhs, rhs)| lhs.zip(rhs));
 
 	pair.clone()
 		.then(just("\n\n").ignore_then(pair).repeated())
@@ -39,6 +48,7 @@ fn parser() -> impl Parser<char, Vec<(Value, Value)>, Error = Simple<char>> {
 		})
 		.then_ignore(just("\n").repeated())
 		.then_ignore(end())
+		.map(|pairs| pairs.into_iter().flatten().collect())
 }
 
 impl PartialEq for Value {
@@ -82,33 +92,45 @@ impl Ord for Value {
 	}
 }
 
-fn main() -> anyhow::Result<()> {
-	let pairs = read("input.txt", parser())?;
+pub struct Day13;
 
-	let mut sum = 0;
-	for (i, (lhs, rhs)) in pairs.iter().enumerate() {
-		if lhs < rhs {
-			sum += i + 1;
-		}
+impl Solution for Day13 {
+	type Input = Vec<(Value, Value)>;
+	type Params = ();
+
+	const EXAMPLE: () = ();
+	const REAL: () = ();
+
+	fn parse(input: &InputSource) -> anyhow::Result<Vec<(Value, Value)>> {
+		read(input, parser())
 	}
-	println!("{sum}");
-
-	let mut values = pairs
-		.iter()
-		.flat_map(|(lhs, rhs)| [lhs, rhs])
-		.collect::<BTreeSet<_>>();
-	let decoder2 = Value::List(vec![Value::List(vec![Value::Number(2)])]);
-	let decoder6 = Value::List(vec![Value::List(vec![Value::Number(6)])]);
-	values.insert(&decoder2);
-	values.insert(&decoder6);
-
-	let mut key = 1;
-	for (i, v) in values.into_iter().enumerate() {
-		if v == &decoder2 || v == &decoder6 {
-			key *= i + 1;
+
+	fn part1(pairs: &Vec<(Value, Value)>, _: &()) -> anyhow::Result<Answer> {
+		let mut sum = 0;
+		for (i, (lhs, rhs)) in pairs.iter().enumerate() {
+			if lhs < rhs {
+				sum += i + 1;
+			}
 		}
+		Ok(sum.into())
 	}
-	println!("{key}");
 
-	Ok(())
+	fn part2(pairs: &Vec<(Value, Value)>, _: &()) -> anyhow::Result<Answer> {
+		let mut values = pairs
+			.iter()
+			.flat_map(|(lhs, rhs)| [lhs, rhs])
+			.collect::<BTreeSet<_>>();
+		let decoder2 = Value::List(vec![Value::List(vec![Value::Number(2)])]);
+		let decoder6 = Value::List(vec![Value::List(vec![Value::Number(6)])]);
+		values.insert(&decoder2);
+		values.insert(&decoder6);
+
+		let mut key = 1;
+		for (i, v) in values.into_iter().enumerate() {
+			if v == &decoder2 || v == &decoder6 {
+				key *= i + 1;
+			}
+		}
+		Ok(key.into())
+	}
 }
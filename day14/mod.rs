@@ -0,0 +1,199 @@
+use crate::{lines, read, Answer, Grid, InputSource, Point, Solution};
+use chumsky::{prelude::*, text::digits};
+use log::debug;
+use std::fmt::{self, Display, Formatter};
+
+fn point() -> impl Parser<char, Point, Error = Simple<char>> + Clone {
+	digits(10)
+		.then_ignore(just(","))
+		.then(digits(10))
+		.map(|(x, y): (String, String)| {
+			Point::new(x.parse().unwrap(), y.parse().unwrap())
+		})
+}
+
+#[derive(Debug)]
+pub struct Path {
+	positions: Vec<Point>
+}
+
+impl Path {
+	fn parser() -> impl Parser<char, Self, Error = Simple<char>> + Clone {
+		point()
+			.then_ignore(just(" -> "))
+			.repeated()
+			.then(point())
+			.map(|(mut positions, last)| {
+				positions.push(last);
+				Self { positions }
+			})
+	}
+}
+
+fn parser() -> impl Parser<char, Vec<Path>, Error = Simple<char>> {
+	lines(Path::parser()).then_ignore(end())
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+enum Tile {
+	#[default]
+	Air,
+	Rock,
+	Sand,
+	Spawner
+}
+
+impl Display for Tile {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::Air => ".",
+			Self::Rock => "#",
+			Self::Sand => "o",
+			Self::Spawner => "+"
+		})
+	}
+}
+
+struct Map {
+	grid: Grid<Tile>,
+	floor: Option<i64>
+}
+
+impl Map {
+	fn new() -> Self {
+		Self {
+			grid: Grid::sparse(),
+			floor: None
+		}
+	}
+
+	/// The lowest row that contains anything but air.
+	fn max_y(&self) -> i64 {
+		self.grid.bounds().map(|bounds| bounds.max.y).unwrap_or(0)
+	}
+
+	fn insert(&mut self, pos: Point, tile: Tile) {
+		self.grid.insert(pos, tile);
+	}
+
+	fn get(&self, pos: Point) -> Tile {
+		match self.floor {
+			Some(floor) if pos.y >= floor => Tile::Rock,
+			_ => self.grid.get(pos).copied().unwrap_or_default()
+		}
+	}
+
+	fn count_sand(&self) -> usize {
+		self.grid
+			.values()
+			.filter(|tile| **tile == Tile::Sand)
+			.count()
+	}
+}
+
+impl Display for Map {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		self.grid.fmt(f)
+	}
+}
+
+/// Move the sand down by one, if possible.
+fn fall(map: &Map, sand: &mut Point) -> bool {
+	for dir in [
+		Point::DOWN,
+		Point::DOWN + Point::LEFT,
+		Point::DOWN + Point::RIGHT
+	] {
+		if map.get(*sand + dir) == Tile::Air {
+			*sand += dir;
+			return true;
+		}
+	}
+	false
+}
+
+fn simulate_sand(map: &mut Map, spawner: Point, max_y: i64) {
+	let mut sand = spawner;
+	while sand.y <= max_y {
+		if sand != spawner {
+			map.grid.remove(sand);
+		}
+
+		let moved = fall(map, &mut sand);
+		map.insert(sand, Tile::Sand);
+
+		if !moved {
+			if sand == spawner {
+				break;
+			}
+			sand = spawner;
+		}
+	}
+}
+
+fn simulate_sand_fast(map: &mut Map, spawner: Point) {
+	let mut q = vec![spawner];
+	while let Some(mut sand) = q.last().copied() {
+		if fall(map, &mut sand) {
+			q.push(sand);
+		} else {
+			map.insert(sand, Tile::Sand);
+			q.remove(q.len() - 1);
+		}
+	}
+}
+
+fn build_map(paths: &[Path]) -> Map {
+	let mut map = Map::new();
+	for path in paths {
+		let mut last: Option<Point> = None;
+		for pos in &path.positions {
+			if let Some(last) = last {
+				for x in last.x.min(pos.x) ..= last.x.max(pos.x) {
+					for y in last.y.min(pos.y) ..= last.y.max(pos.y) {
+						map.insert(Point::new(x, y), Tile::Rock);
+					}
+				}
+			}
+			last = Some(*pos);
+		}
+	}
+	map
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+	type Input = Vec<Path>;
+	type Params = ();
+
+	const EXAMPLE: () = ();
+	const REAL: () = ();
+
+	fn parse(input: &InputSource) -> anyhow::Result<Vec<Path>> {
+		read(input, parser())
+	}
+
+	fn part1(paths: &Vec<Path>, _: &()) -> anyhow::Result<Answer> {
+		let mut map = build_map(paths);
+		let spawner = Point::new(500, 0);
+		map.insert(spawner, Tile::Spawner);
+		debug!("\n{map}");
+
+		let max_y = map.max_y();
+		simulate_sand(&mut map, spawner, max_y);
+		debug!("\n{map}");
+		Ok((map.count_sand() - 1).into())
+	}
+
+	fn part2(paths: &Vec<Path>, _: &()) -> anyhow::Result<Answer> {
+		let mut map = build_map(paths);
+		let spawner = Point::new(500, 0);
+		map.insert(spawner, Tile::Spawner);
+
+		map.floor = Some(map.max_y() + 2);
+		simulate_sand_fast(&mut map, spawner);
+		debug!("\n{map}");
+		Ok(map.count_sand().into())
+	}
+}
@@ -1,5 +1,7 @@
-use aoc22::read;
+use crate::{lines, read, Answer, InputSource, Solution};
+use anyhow::bail;
 use chumsky::{prelude::*, text::digits};
+use log::{debug, info, warn};
 use std::{
 	collections::{BTreeMap, HashSet},
 	ops::RangeInclusive
@@ -32,7 +34,7 @@ impl Position {
 }
 
 #[derive(Clone, Copy, Debug)]
-struct Sensor {
+pub struct Sensor {
 	pos: Position,
 	nearest_beacon: Position
 }
@@ -51,20 +53,17 @@ impl Sensor {
 }
 
 fn parser() -> impl Parser<char, Vec<Sensor>, Error = Simple<char>> {
-	Sensor::parser()
-		.then_ignore(just("\n"))
-		.repeated()
-		.at_least(1)
-		.then_ignore(end())
+	lines(Sensor::parser()).then_ignore(end())
 }
 
-// const LINE_Y: i64 = 10;
-// const MAX: i64 = 20;
-// const MIN: i64 = 0;
-
-const LINE_Y: i64 = 2000000;
-const MAX: i64 = 4000000;
-const MIN: i64 = 0;
+pub struct Params {
+	/// The row in which to count the positions where a beacon cannot be present.
+	line_y: i64,
+	/// The smallest x and y coordinate the distress beacon can have.
+	min: i64,
+	/// The largest x and y coordinate the distress beacon can have.
+	max: i64
+}
 
 #[derive(Debug, Default)]
 struct Row {
@@ -128,8 +127,8 @@ impl Row {
 		blocked
 	}
 
-	fn find_free(&self) -> Vec<i64> {
-		let mut last = MIN - 1;
+	fn find_free(&self, min: i64) -> Vec<i64> {
+		let mut last = min - 1;
 		let mut free = Vec::new();
 		for r in &self.ranges {
 			if *r.start() > last + 1 {
@@ -164,62 +163,84 @@ impl Map {
 			.unwrap_or_default()
 	}
 
-	fn find_free(&self) -> Vec<Position> {
+	fn find_free(&self, params: &Params) -> Vec<Position> {
 		let mut positions = vec![];
-		for y in MIN ..= MAX {
+		for y in params.min ..= params.max {
 			if y % 100000 == 0 {
-				println!("find_free(): y={y}");
+				info!("find_free(): y={y}");
 			}
 
 			if let Some(row) = self.map.get(&y) {
-				for x in row.find_free() {
+				for x in row.find_free(params.min) {
 					positions.push(Position { x, y });
 				}
 			} else {
-				eprintln!("Missing row for y={y}");
+				warn!("Missing row for y={y}");
 			}
 		}
 		positions
 	}
 }
 
-fn main() -> anyhow::Result<()> {
-	let sensors = read("input.txt", parser())?;
+pub struct Day15;
+
+impl Solution for Day15 {
+	type Input = Vec<Sensor>;
+	type Params = Params;
+
+	const EXAMPLE: Params = Params {
+		line_y: 10,
+		min: 0,
+		max: 20
+	};
+	const REAL: Params = Params {
+		line_y: 2000000,
+		min: 0,
+		max: 4000000
+	};
+
+	fn parse(input: &InputSource) -> anyhow::Result<Vec<Sensor>> {
+		read(input, parser())
+	}
 
-	let mut map = Map::default();
-	let mut beacons_in_line = HashSet::<Position>::new();
-	for s in &sensors {
-		if s.nearest_beacon.y == LINE_Y {
-			beacons_in_line.insert(s.nearest_beacon);
-		}
+	fn part1(sensors: &Vec<Sensor>, params: &Params) -> anyhow::Result<Answer> {
+		let mut map = Map::default();
+		let mut beacons_in_line = HashSet::<Position>::new();
+		for s in sensors {
+			if s.nearest_beacon.y == params.line_y {
+				beacons_in_line.insert(s.nearest_beacon);
+			}
 
-		let beacon_dist = s.pos.manhattan_dist(&s.nearest_beacon);
-		let line_dist = s.pos.y.abs_diff(LINE_Y);
-		if line_dist > beacon_dist {
-			continue;
+			let beacon_dist = s.pos.manhattan_dist(&s.nearest_beacon);
+			let line_dist = s.pos.y.abs_diff(params.line_y);
+			if line_dist > beacon_dist {
+				continue;
+			}
+			let xrange = (beacon_dist - line_dist) as i64;
+			map.add_x_range(s.pos.x - xrange ..= s.pos.x + xrange, params.line_y);
 		}
-		let xrange = (beacon_dist - line_dist) as i64;
-		map.add_x_range(s.pos.x - xrange ..= s.pos.x + xrange, LINE_Y);
+		Ok((map.blocked(params.line_y) - beacons_in_line.len() as u64).into())
 	}
-	println!("{}", map.blocked(LINE_Y) - beacons_in_line.len() as u64);
-
-	// part 2
-
-	map = Map::default();
-	for s in &sensors {
-		println!(" - {s:?}");
-		let beacon_dist = s.pos.manhattan_dist(&s.nearest_beacon) as i64;
-		for y in (s.pos.y - beacon_dist).max(MIN) ..= (s.pos.y + beacon_dist).min(MAX) {
-			let xrange = beacon_dist - s.pos.y.abs_diff(y) as i64;
-			map.add_x_range(
-				(s.pos.x - xrange).max(MIN) ..= (s.pos.x + xrange).min(MAX),
-				y
-			);
+
+	fn part2(sensors: &Vec<Sensor>, params: &Params) -> anyhow::Result<Answer> {
+		let mut map = Map::default();
+		for s in sensors {
+			debug!(" - {s:?}");
+			let beacon_dist = s.pos.manhattan_dist(&s.nearest_beacon) as i64;
+			let ymin = (s.pos.y - beacon_dist).max(params.min);
+			let ymax = (s.pos.y + beacon_dist).min(params.max);
+			for y in ymin ..= ymax {
+				let xrange = beacon_dist - s.pos.y.abs_diff(y) as i64;
+				map.add_x_range(
+					(s.pos.x - xrange).max(params.min)
+						..= (s.pos.x + xrange).min(params.max),
+					y
+				);
+			}
+		}
+		match map.find_free(params).as_slice() {
+			[free] => Ok((free.x * 4000000 + free.y).into()),
+			free => bail!("Expected exactly one free position, found {free:?}")
 		}
 	}
-	for free in map.find_free() {
-		println!("{free:?} = {}", free.x * 4000000 + free.y);
-	}
-
-	Ok(())
 }
@@ -1,17 +1,18 @@
-use aoc22::read;
+use crate::{lines, read, Answer, InputSource, Solution};
 use bit_vec::BitVec;
 use chumsky::{
 	prelude::*,
 	text::{digits, ident}
 };
 use indexmap::IndexMap;
+use log::{debug, info, trace};
 use std::{
 	collections::HashMap,
 	hash::{Hash, Hasher}
 };
 
 #[derive(Debug)]
-struct Vertex {
+pub struct Vertex {
 	flow_rate: u32,
 	adj: Vec<Edge>
 }
@@ -41,10 +42,7 @@ fn parser() -> impl Parser<char, IndexMap<String, Vertex>, Error = Simple<char>>
 		)
 		.then(adj)
 		.map(|((id, flow_rate), adj)| (id, Vertex { flow_rate, adj }));
-	vertex
-		.then_ignore(just("\n"))
-		.repeated()
-		.at_least(1)
+	lines(vertex)
 		.then_ignore(end())
 		.map(|vec| vec.into_iter().collect())
 }
@@ -106,7 +104,7 @@ fn bfs<'a>(
 	while remaining > 0 {
 		let q = qs.remove(&remaining).unwrap();
 		let q_len = q.len();
-		println!(" remaining: {remaining}, q: {}", q.len());
+		info!(" remaining: {remaining}, q: {}", q.len());
 
 		for (state, pressure) in q {
 			if state.open_count == max_open_vertices {
@@ -118,7 +116,7 @@ fn bfs<'a>(
 			}
 
 			if init_q_len != q_len && state.vertex == "AA" && state.flow_rate == 0 {
-				//eprintln!("skipping {state:?}");
+				trace!("skipping {state:?}");
 				continue;
 			}
 
@@ -162,79 +160,112 @@ fn inline_edge(v: &str, vertex: &mut Vertex, edge: &Edge) {
 	});
 }
 
-fn main() -> anyhow::Result<()> {
-	let mut vertices = read("input.txt", parser())?;
+pub struct Params {
+	/// The minutes until the volcano erupts.
+	minutes: u32,
+	/// The minutes that are left after teaching an elephant to help.
+	minutes_with_elephant: u32
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+	type Input = IndexMap<String, Vertex>;
+	type Params = Params;
+
+	const EXAMPLE: Params = Self::REAL;
+	const REAL: Params = Params {
+		minutes: 30,
+		minutes_with_elephant: 26
+	};
+
+	fn parse(input: &InputSource) -> anyhow::Result<IndexMap<String, Vertex>> {
+		let mut vertices = read(input, parser())?;
 
-	for v in vertices.keys().cloned().collect::<Vec<_>>() {
-		let vertex = &vertices[&v];
-		if vertex.flow_rate > 0 || vertex.adj.len() > 2 {
-			continue;
+		for v in vertices.keys().cloned().collect::<Vec<_>>() {
+			let vertex = &vertices[&v];
+			if vertex.flow_rate > 0 || vertex.adj.len() > 2 {
+				continue;
+			}
+			let vertex = vertices.remove(&v).unwrap();
+			match vertex.adj.len() {
+				0 => {},
+				1 => vertices
+					.get_mut(&vertex.adj.first().unwrap().next)
+					.unwrap()
+					.adj
+					.retain(|edge| edge.next != v),
+				2 => {
+					let first = vertex.adj.first().unwrap();
+					let second = vertex.adj.last().unwrap();
+					inline_edge(&v, vertices.get_mut(&first.next).unwrap(), second);
+					inline_edge(&v, vertices.get_mut(&second.next).unwrap(), first);
+				},
+				_ => unreachable!()
+			}
 		}
-		let vertex = vertices.remove(&v).unwrap();
-		match vertex.adj.len() {
-			0 => {},
-			1 => vertices
-				.get_mut(&vertex.adj.first().unwrap().next)
-				.unwrap()
-				.adj
-				.retain(|edge| edge.next != v),
-			2 => {
-				let first = vertex.adj.first().unwrap();
-				let second = vertex.adj.last().unwrap();
-				inline_edge(&v, vertices.get_mut(&first.next).unwrap(), second);
-				inline_edge(&v, vertices.get_mut(&second.next).unwrap(), first);
-			},
-			_ => unreachable!()
+		for (key, v) in &vertices {
+			debug!("{key}:\t{v:?}");
 		}
+
+		Ok(vertices)
 	}
-	for (key, v) in &vertices {
-		println!("{key}:\t{v:?}");
-	}
 
-	let max_open_vertices = vertices.values().filter(|v| v.flow_rate > 0).count();
-
-	let mut q = HashMap::new();
-	q.insert(
-		State {
-			vertex: "AA",
-			flow_rate: 0,
-			open: Default::default(),
-			open_count: 0
-		},
-		0
-	);
-	let q = bfs(&vertices, max_open_vertices, q, 30);
-	let max = q.iter().map(|(_, pressure)| pressure).max().unwrap();
-	println!("{max}");
-
-	// part 2
-
-	let mut q = HashMap::new();
-	q.insert(
-		State {
-			vertex: "AA",
-			flow_rate: 0,
-			open: Default::default(),
-			open_count: 0
-		},
-		0
-	);
-	let q = bfs(&vertices, max_open_vertices, q, 26);
-
-	let mut elephant_q = HashMap::new();
-	for (state, pressure) in q {
-		let key = State {
-			vertex: "AA",
-			flow_rate: 0,
-			open: state.open,
-			open_count: state.open_count
-		};
-		let value: &mut u32 = elephant_q.entry(key).or_default();
-		*value = pressure.max(*value);
+	fn part1(
+		vertices: &IndexMap<String, Vertex>,
+		params: &Params
+	) -> anyhow::Result<Answer> {
+		let max_open_vertices = vertices.values().filter(|v| v.flow_rate > 0).count();
+
+		let mut q = HashMap::new();
+		q.insert(
+			State {
+				vertex: "AA",
+				flow_rate: 0,
+				open: Default::default(),
+				open_count: 0
+			},
+			0
+		);
+		let q = bfs(vertices, max_open_vertices, q, params.minutes);
+		Ok((*q.values().max().unwrap()).into())
 	}
-	let q = bfs(&vertices, max_open_vertices, elephant_q, 26);
-	let max = q.iter().map(|(_, pressure)| pressure).max().unwrap();
-	println!("{max}");
 
-	Ok(())
+	fn part2(
+		vertices: &IndexMap<String, Vertex>,
+		params: &Params
+	) -> anyhow::Result<Answer> {
+		let max_open_vertices = vertices.values().filter(|v| v.flow_rate > 0).count();
+
+		let mut q = HashMap::new();
+		q.insert(
+			State {
+				vertex: "AA",
+				flow_rate: 0,
+				open: Default::default(),
+				open_count: 0
+			},
+			0
+		);
+		let q = bfs(vertices, max_open_vertices, q, params.minutes_with_elephant);
+
+		let mut elephant_q = HashMap::new();
+		for (state, pressure) in q {
+			let key = State {
+				vertex: "AA",
+				flow_rate: 0,
+				open: state.open,
+				open_count: state.open_count
+			};
+			let value: &mut u32 = elephant_q.entry(key).or_default();
+			*value = pressure.max(*value);
+		}
+		let q = bfs(
+			vertices,
+			max_open_vertices,
+			elephant_q,
+			params.minutes_with_elephant
+		);
+		Ok((*q.values().max().unwrap()).into())
+	}
 }
@@ -0,0 +1,288 @@
+use crate::{read, Answer, Grid, InputSource, Point, Solution};
+use chumsky::prelude::*;
+use log::{debug, info, trace};
+use std::fmt::{self, Debug, Formatter};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+	Left,
+	Right
+}
+
+#[derive(Clone)]
+pub struct Wind(Vec<Direction>, usize);
+
+impl Iterator for Wind {
+	type Item = Direction;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let next = self.0[self.1];
+		self.1 = (self.1 + 1) % self.0.len();
+		Some(next)
+	}
+}
+
+fn parser() -> impl Parser<char, Wind, Error = Simple<char>> {
+	choice((
+		just(">").map(|_| Direction::Right),
+		just("<").map(|_| Direction::Left)
+	))
+	.recover_with(skip_then_retry_until(['\n']))
+	.repeated()
+	.at_least(1)
+	.then_ignore(just("\n"))
+	.then_ignore(end())
+	.map(|wind| Wind(wind, 0))
+}
+
+/// The chamber. Rows are counted upwards from the floor, which is row 0, and are stored
+/// with negative y on the grid so that it renders the right way up.
+struct Tetris {
+	grid: Grid<Rock>,
+	max_height: usize
+}
+
+impl Tetris {
+	fn new() -> Self {
+		Self {
+			grid: Grid::dense(),
+			max_height: 0
+		}
+	}
+
+	fn point(x: usize, row: usize) -> Point {
+		Point::new(x as i64, -(row as i64))
+	}
+
+	/// Whether a cell is inside the chamber and not occupied. Cells below the top of
+	/// a column can be free, so that rocks can slide underneath overhangs.
+	fn is_free(&self, x: usize, row: usize) -> bool {
+		row > 0 && x < 7 && !self.grid.contains(Self::point(x, row))
+	}
+
+	fn occupy(&mut self, rock: Rock, x: usize, row: usize) {
+		if !self.is_free(x, row) {
+			panic!("{x}, {row} is already occupied");
+		}
+		self.grid.insert(Self::point(x, row), rock);
+		self.max_height = self.max_height.max(row);
+	}
+}
+
+impl Debug for Tetris {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		for row in (1 ..= self.max_height).rev() {
+			write!(f, "│")?;
+			for x in 0 .. 7 {
+				match self.grid.get(Self::point(x, row)) {
+					Some(rock) => write!(f, "\x1B[{}m██\x1B[0m", 31 + *rock as u8)?,
+					None => write!(f, "  ")?
+				}
+			}
+			writeln!(f, "│")?;
+		}
+		f.write_str("┕━━━━━━━━━━━━━━┙")
+	}
+}
+
+struct Size {
+	width: usize,
+	height: usize
+}
+
+macro_rules! rocks {
+	($($ident:ident : { $($line:literal),+ }),+) => {
+		#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+		#[repr(u8)]
+		enum Rock {
+			$($ident),+
+		}
+
+		impl Rock {
+			fn from_index(idx: usize) -> Self {
+				let mut len = 0;
+				$(stringify!($ident); len += 1;)+
+				let idx = idx % len;
+				let mut curr = 0;
+				$(
+					if curr == idx {
+						return Self::$ident;
+					}
+					curr += 1;
+				)+
+				unreachable!("index {idx} cannot be reached when len is {len} (curr is {curr})")
+			}
+
+			const fn lines(self) -> &'static [&'static [u8]] {
+				match self {
+					$(Self::$ident => &[$($line),+]),+
+				}
+			}
+
+			#[allow(unused_must_use)]
+			fn size(self) -> Size {
+				match self {
+					$(Self::$ident => Size {
+						width: { $($line.len());+ },
+						height: [$($line),+].len()
+					}),+
+				}
+			}
+
+
+			/// All cells that the rock occupies when its bottom left corner is at the
+			/// given position. The lines of the rock are listed from the bottom up.
+			fn cells(self, x: usize, row: usize) -> impl Iterator<Item = (usize, usize)> {
+				let lines = self.lines();
+				let size = self.size();
+				(0 .. size.height).flat_map(move |i| {
+					(0 .. size.width)
+						.filter(move |j| lines[i][*j] != b' ')
+						.map(move |j| (x + j, row + i))
+				})
+			}
+
+			fn intersects(self, tetris: &Tetris, x: usize, row: usize) -> bool {
+				self.cells(x, row).any(|(x, row)| !tetris.is_free(x, row))
+			}
+
+			fn freeze(self, tetris: &mut Tetris, x: usize, row: usize) {
+				for (x, row) in self.cells(x, row) {
+					tetris.occupy(self, x, row);
+				}
+			}
+		}
+	};
+}
+
+rocks! {
+	HorizLine: {
+		b"####"
+	},
+	Plus: {
+		b" # ",
+		b"###",
+		b" # "
+	},
+	Corner: {
+		b"###",
+		b"  #",
+		b"  #"
+	},
+	VertLine: {
+		b"#",
+		b"#",
+		b"#",
+		b"#"
+	},
+	Square: {
+		b"##",
+		b"##"
+	}
+}
+
+fn apply_wind(wind: &mut Wind, rock: Rock, x: &mut usize) {
+	let Size { width, .. } = rock.size();
+	let dir = wind.next().unwrap();
+	match dir {
+		Direction::Left => *x = x.saturating_sub(1),
+		Direction::Right => {
+			if *x + 1 + width <= 7 {
+				*x += 1;
+			}
+		},
+	}
+}
+
+fn simulate<I>(
+	wind: &mut Wind,
+	tetris: &mut Tetris,
+	range: I,
+	stop_at_reset: bool
+) -> Option<usize>
+where
+	I: Iterator<Item = usize>
+{
+	for i in range {
+		let rock = Rock::from_index(i);
+		if rock == Rock::HorizLine && wind.1 == 0 {
+			info!("Reset at i={i}");
+			if stop_at_reset {
+				return Some(i);
+			}
+		}
+
+		let mut x: usize = 2;
+		let mut y: usize = tetris.max_height + 4;
+		trace!("{rock:?} starts falling at ({x}, {y})");
+		loop {
+			let backup = x;
+			apply_wind(wind, rock, &mut x);
+			if rock.intersects(tetris, x, y) {
+				x = backup;
+			} else {
+				trace!("{rock:?} was pushed to     ({x}, {y})");
+			}
+			if rock.intersects(tetris, x, y - 1) {
+				break;
+			}
+			y -= 1;
+			trace!("{rock:?} falls down to     ({x}, {y})");
+		}
+
+		trace!("{rock:?} freezes at        ({x}, {y})");
+		rock.freeze(tetris, x, y);
+
+		trace!("i={i} (rock: {rock:?}):\n{tetris:?}");
+	}
+	None
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+	type Input = Wind;
+	type Params = ();
+
+	const EXAMPLE: () = ();
+	const REAL: () = ();
+
+	fn parse(input: &InputSource) -> anyhow::Result<Wind> {
+		read(input, parser())
+	}
+
+	fn part1(wind: &Wind, _: &()) -> anyhow::Result<Answer> {
+		let mut wind = wind.clone();
+		let mut tetris = Tetris::new();
+		simulate(&mut wind, &mut tetris, 0 .. 2022, false);
+		Ok(tetris.max_height.into())
+	}
+
+	fn part2(wind: &Wind, _: &()) -> anyhow::Result<Answer> {
+		let mut wind = wind.clone();
+		let mut idx = 2022;
+		let mut tetris = Tetris::new();
+		simulate(&mut wind, &mut tetris, 0 .. idx, false);
+
+		idx = simulate(&mut wind, &mut tetris, idx .., true).unwrap();
+		debug!("idx={idx}");
+
+		let lcm = wind.0.len() * 5 * 3;
+		if lcm < 2022 {
+			tetris = Tetris::new();
+			wind.1 = 0;
+			idx = 0;
+		}
+		simulate(&mut wind, &mut tetris, idx .. lcm, false);
+		let lcm_height = tetris.max_height;
+		simulate(&mut wind, &mut tetris, lcm .. lcm * 2, false);
+		let lcm2_height = tetris.max_height;
+		let multiplier = 1000000000000 / lcm;
+		let rem = 1000000000000 % lcm;
+		simulate(&mut wind, &mut tetris, lcm .. lcm + rem, false);
+		debug!("lcm={lcm}, lcm_height={lcm_height}, lcm2_height={lcm2_height}");
+		debug!("multiplier={multiplier}, rem={rem}");
+		debug!("max_height={}", tetris.max_height);
+		Ok(((lcm2_height - lcm_height) * (multiplier - 1) + tetris.max_height).into())
+	}
+}
@@ -1,4 +1,4 @@
-use aoc22::read;
+use crate::{lines, read, Answer, InputSource, Solution};
 use chumsky::{prelude::*, text::digits};
 use indexmap::IndexSet;
 use std::{
@@ -7,7 +7,7 @@ use std::{
 };
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Position<T = i32> {
+pub struct Position<T = i32> {
 	x: T,
 	y: T,
 	z: T
@@ -67,56 +67,65 @@ fn parser() -> impl Parser<char, IndexSet<Position>, Error = Simple<char>> {
 			y: y.parse().unwrap(),
 			z: z.parse().unwrap()
 		});
-	pos.then_ignore(just("\n"))
-		.repeated()
-		.at_least(1)
+	lines(pos)
+		.then_ignore(end())
 		.map(|vec| vec.into_iter().collect())
 }
 
-fn main() -> anyhow::Result<()> {
-	let cubes = read("input.txt", parser())?;
+pub struct Day18;
 
-	let mut count: u64 = 0;
-	for c in &cubes {
-		for adj in c.adjacent() {
-			if !cubes.contains(&adj) {
-				count += 1;
+impl Solution for Day18 {
+	type Input = IndexSet<Position>;
+	type Params = ();
+
+	const EXAMPLE: () = ();
+	const REAL: () = ();
+
+	fn parse(input: &InputSource) -> anyhow::Result<IndexSet<Position>> {
+		read(input, parser())
+	}
+
+	fn part1(cubes: &IndexSet<Position>, _: &()) -> anyhow::Result<Answer> {
+		let mut count: u64 = 0;
+		for c in cubes {
+			for adj in c.adjacent() {
+				if !cubes.contains(&adj) {
+					count += 1;
+				}
 			}
 		}
+		Ok(count.into())
 	}
-	println!("{count}");
-
-	// part 2
 
-	let (max_x, max_y, max_z) = cubes.iter().fold((1, 1, 1), |(x, y, z), cube| {
-		(x.max(cube.x + 1), y.max(cube.y + 1), z.max(cube.z + 1))
-	});
-	let mut count: u64 = 0;
-	let mut visited = HashSet::new();
-	let mut q = VecDeque::new();
-	q.push_back(Position::new(-1, -1, -1));
-	while let Some(steam) = q.pop_front() {
-		if steam.x > max_x
-			|| steam.y > max_y
-			|| steam.z > max_z
-			|| steam.x < -1
-			|| steam.y < -1
-			|| steam.z < -1
-			|| visited.contains(&steam)
-		{
-			continue;
-		}
-		visited.insert(steam);
+	fn part2(cubes: &IndexSet<Position>, _: &()) -> anyhow::Result<Answer> {
+		let (max_x, max_y, max_z) = cubes.iter().fold((1, 1, 1), |(x, y, z), cube| {
+			(x.max(cube.x + 1), y.max(cube.y + 1), z.max(cube.z + 1))
+		});
+		let mut count: u64 = 0;
+		let mut visited = HashSet::new();
+		let mut q = VecDeque::new();
+		q.push_back(Position::new(-1, -1, -1));
+		while let Some(steam) = q.pop_front() {
+			if steam.x > max_x
+				|| steam.y > max_y
+				|| steam.z > max_z
+				|| steam.x < -1
+				|| steam.y < -1
+				|| steam.z < -1
+				|| visited.contains(&steam)
+			{
+				continue;
+			}
+			visited.insert(steam);
 
-		for adj in steam.adjacent() {
-			if cubes.contains(&adj) {
-				count += 1;
-			} else {
-				q.push_back(adj);
+			for adj in steam.adjacent() {
+				if cubes.contains(&adj) {
+					count += 1;
+				} else {
+					q.push_back(adj);
+				}
 			}
 		}
+		Ok(count.into())
 	}
-	println!("{count}");
-
-	Ok(())
 }
@@ -1,5 +1,6 @@
-use aoc22::read;
+use crate::{read, Answer, InputSource, Solution};
 use chumsky::{prelude::*, text::digits};
+use log::{debug, info};
 use std::{
 	collections::{HashMap, HashSet, VecDeque},
 	fmt::{self, Debug, Formatter},
@@ -28,7 +29,7 @@ impl Resource {
 }
 
 #[derive(Debug)]
-struct Blueprint {
+pub struct Blueprint {
 	id: u64,
 	factory: HashMap<Resource, ResourceMap>
 }
@@ -74,10 +75,14 @@ impl Blueprint {
 
 fn parser() -> impl Parser<char, Vec<Blueprint>, Error = Simple<char>> {
 	Blueprint::parser()
-		.then_ignore(just("\n").repeated().at_least(1))
+		.then_ignore(just("\n"))
+		.map(Some)
+		.recover_with(skip_until(['\n'], |_| None).consume_end())
+		.then_ignore(just("\n").repeated())
 		.repeated()
 		.at_least(1)
 		.then_ignore(end())
+		.map(|blueprints| blueprints.into_iter().flatten().collect())
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
@@ -226,13 +231,13 @@ impl Hash for State<'_> {
 }
 
 impl<'a> State<'a> {
-	fn new(blueprint: &'a Blueprint) -> Self {
+	fn new(blueprint: &'a Blueprint, minutes: u16) -> Self {
 		Self {
 			blueprint,
 			parent: None,
 			robots: vec![(Resource::Ore, 1)].into(),
 			resources: ResourceMap::default(),
-			remaining: 24
+			remaining: minutes
 		}
 	}
 }
@@ -294,7 +299,7 @@ fn bfs(initial: State<'_>) -> State<'_> {
 		if state.remaining < last {
 			last = state.remaining;
 			q.inserted.retain(|state| state.remaining < last);
-			println!(
+			info!(
 				"remaining: {last} (q: {}, {})",
 				q.q.len() + 1,
 				q.inserted.len()
@@ -330,34 +335,56 @@ fn bfs(initial: State<'_>) -> State<'_> {
 	best
 }
 
-fn main() -> anyhow::Result<()> {
-	let blueprints = read("input.txt", parser())?;
-
-	let mut total: u64 = 0;
-	for blueprint in &blueprints {
-		let state = bfs(State::new(blueprint));
-		println!(
-			"Blueprint {} has mined {} geodes",
-			blueprint.id, state.resources.geode
-		);
-		println!("{state:?}");
-		total += blueprint.id * state.resources.geode as u64;
+pub struct Params {
+	/// The minutes to mine geodes for in part 1.
+	minutes: u16,
+	/// The minutes to mine geodes for in part 2.
+	minutes_part2: u16,
+	/// The amount of blueprints that the elephants didn't eat.
+	blueprints_part2: usize
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+	type Input = Vec<Blueprint>;
+	type Params = Params;
+
+	const EXAMPLE: Params = Self::REAL;
+	const REAL: Params = Params {
+		minutes: 24,
+		minutes_part2: 32,
+		blueprints_part2: 3
+	};
+
+	fn parse(input: &InputSource) -> anyhow::Result<Vec<Blueprint>> {
+		read(input, parser())
 	}
-	println!("{total}");
-
-	// part 2
-	let mut total: u64 = 1;
-	for blueprint in blueprints.iter().take(3) {
-		let mut initial = State::new(blueprint);
-		initial.remaining = 32;
-		let state = bfs(initial);
-		println!(
-			"Blueprint {} has mined {} geodes",
-			blueprint.id, state.resources.geode
-		);
-		total *= state.resources.geode as u64;
+
+	fn part1(blueprints: &Vec<Blueprint>, params: &Params) -> anyhow::Result<Answer> {
+		let mut total: u64 = 0;
+		for blueprint in blueprints {
+			let state = bfs(State::new(blueprint, params.minutes));
+			info!(
+				"Blueprint {} has mined {} geodes",
+				blueprint.id, state.resources.geode
+			);
+			debug!("{state:?}");
+			total += blueprint.id * state.resources.geode as u64;
+		}
+		Ok(total.into())
 	}
-	println!("{total}");
 
-	Ok(())
+	fn part2(blueprints: &Vec<Blueprint>, params: &Params) -> anyhow::Result<Answer> {
+		let mut total: u64 = 1;
+		for blueprint in blueprints.iter().take(params.blueprints_part2) {
+			let state = bfs(State::new(blueprint, params.minutes_part2));
+			info!(
+				"Blueprint {} has mined {} geodes",
+				blueprint.id, state.resources.geode
+			);
+			total *= state.resources.geode as u64;
+		}
+		Ok(total.into())
+	}
 }
@@ -0,0 +1,289 @@
+pub mod reference;
+pub mod trace;
+mod tree;
+
+use crate::{read, Answer, InputSource, Solution};
+use anyhow::{bail, Context as _};
+use chumsky::prelude::*;
+use clap::ValueEnum;
+use log::{debug, info, trace};
+use num_traits::{PrimInt, Signed};
+use std::{
+	fmt::{self, Debug, Display, Formatter},
+	ops::Mul
+};
+use trace::{Move, Trace};
+use tree::Tree;
+
+pub type T = i64;
+
+fn parser() -> impl Parser<char, Vec<T>, Error = Simple<char>> {
+	let digit = one_of(['-', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9'])
+		.repeated()
+		.at_least(1)
+		.collect::<String>()
+		.try_map(|digits, span| {
+			digits
+				.parse()
+				.map_err(|err| Simple::custom(span, format!("Invalid number: {err}")))
+		});
+	digit
+		.then_ignore(one_of([' ', '\n']).repeated().at_least(1))
+		.map(Some)
+		.recover_with(skip_until([' ', '\n'], |_| None).consume_end())
+		.repeated()
+		.at_least(1)
+		.then_ignore(end())
+		.map(|values| values.into_iter().flatten().collect())
+}
+
+/// The integers a [`List`] can hold.
+pub trait Int: PrimInt + Signed + Debug + Display {}
+
+impl<I: PrimInt + Signed + Debug + Display> Int for I {}
+
+/// The numbers in their circular order.
+pub struct List<I = T> {
+	/// The values in the order of the input.
+	values: Vec<I>,
+	/// The order the values are in now.
+	tree: Tree,
+	/// Every move made so far, if we are asked to record them.
+	trace: Option<Trace<I>>
+}
+
+impl<I: Int> Debug for List<I> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_list()
+			.entries(self.tree.order().into_iter().map(|idx| self.values[idx]))
+			.finish()
+	}
+}
+
+impl<I: Int> Mul<I> for List<I> {
+	type Output = Self;
+
+	fn mul(mut self, rhs: I) -> Self::Output {
+		for value in &mut self.values {
+			*value = *value * rhs;
+		}
+		self
+	}
+}
+
+impl<I: Int> List<I> {
+	pub fn new(input: &[I]) -> Self {
+		Self {
+			values: input.to_vec(),
+			tree: Tree::new(input.len()),
+			trace: None
+		}
+	}
+
+	/// The values in the order of the input.
+	pub fn values(&self) -> &[I] {
+		&self.values
+	}
+
+	/// Record every move from now on.
+	pub fn traced(mut self) -> Self {
+		self.trace = Some(Trace { moves: Vec::new() });
+		self
+	}
+
+	/// The moves made since [`traced`](Self::traced) was called.
+	pub fn trace(&self) -> Option<&Trace<I>> {
+		self.trace.as_ref()
+	}
+
+	/// Move the value that was at `idx` in the input, forward if `forward` is true and
+	/// backward otherwise.
+	fn shift(&mut self, idx: usize, forward: bool) {
+		let len = self.values.len();
+		let value = self.values[idx];
+		// there are only len - 1 other values to move past
+		let steps = value.to_i128().unwrap().rem_euclid(len as i128 - 1) as usize;
+		let from = self.tree.rank(idx);
+		let to = match forward {
+			true => (from + steps) % (len - 1),
+			false => (from + (len - 1 - steps)) % (len - 1)
+		};
+		// moving to the front and to the back is the same in a circle, but the puzzle
+		// statement puts the value at the back. A value that doesn't move stays put.
+		let to = match to {
+			_ if steps == 0 => from,
+			0 => len - 1,
+			to => to
+		};
+		trace!("{value} moves from {from} to {to}");
+		self.tree.move_to(idx, to);
+		trace!("{self:?}");
+		if let Some(trace) = &mut self.trace {
+			trace.moves.push(Move { value, from, to });
+		}
+	}
+
+	/// Move every value as many positions forward or backward as the value says, in
+	/// the order of the input. Each move takes `O(log n)` time.
+	pub fn mix(&mut self) {
+		debug!("mixing ...");
+		if self.values.len() < 2 {
+			return;
+		}
+		for idx in 0 .. self.values.len() {
+			self.shift(idx, true);
+		}
+	}
+
+	/// Undo [`mix`](Self::mix), by moving every value back in reverse order. The values
+	/// end up in their original circular order.
+	pub fn unmix(&mut self) {
+		debug!("unmixing ...");
+		if self.values.len() < 2 {
+			return;
+		}
+		for idx in (0 .. self.values.len()).rev() {
+			self.shift(idx, false);
+		}
+	}
+
+	/// The original indices of all values in the circular order they are in now,
+	/// starting with the first value of the input.
+	pub fn order(&self) -> Vec<usize> {
+		let mut order = self.tree.order();
+		let start = order.iter().position(|idx| *idx == 0).unwrap_or(0);
+		order.rotate_left(start);
+		order
+	}
+
+	/// The values at the offsets after the first occurrence of the anchor.
+	pub fn coordinates(&self, anchor: I, offsets: &[usize]) -> anyhow::Result<Vec<I>> {
+		let Some(anchor_idx) = self.values.iter().position(|value| *value == anchor)
+		else {
+			bail!("There is no {anchor} in the list");
+		};
+		let rank = self.tree.rank(anchor_idx);
+		Ok(offsets
+			.iter()
+			.map(|offset| self.values[self.tree.at((rank + offset) % self.tree.len())])
+			.collect())
+	}
+
+	/// The sum of the values at the offsets after the anchor.
+	pub fn sum(&self, anchor: I, offsets: &[usize]) -> anyhow::Result<I> {
+		self.coordinates(anchor, offsets)?
+			.into_iter()
+			.try_fold(I::zero(), |sum, value| sum.checked_add(&value))
+			.context("The sum overflows")
+	}
+}
+
+/// How to decrypt the numbers.
+#[derive(Clone, Debug)]
+pub struct Decryption {
+	/// Every number is multiplied by the key before mixing.
+	pub key: i128,
+	/// How often the numbers are mixed.
+	pub rounds: usize,
+	/// The number the offsets are counted from, before it is multiplied by the key.
+	pub anchor: i128,
+	/// The positions after the anchor whose values are added up.
+	pub offsets: Vec<usize>
+}
+
+impl Decryption {
+	pub fn part1() -> Self {
+		Self {
+			key: 1,
+			rounds: 1,
+			anchor: 0,
+			offsets: vec![1000, 2000, 3000]
+		}
+	}
+
+	pub fn part2() -> Self {
+		Self {
+			key: 811589153,
+			rounds: 10,
+			..Self::part1()
+		}
+	}
+
+	/// The numbers multiplied by the key. Fails if any number doesn't fit into `I`.
+	pub fn list<I: Int>(&self, input: &[T]) -> anyhow::Result<List<I>> {
+		let key = convert::<I>(self.key)?;
+		let values = input
+			.iter()
+			.map(|value| multiply(*value as i128, key))
+			.collect::<anyhow::Result<Vec<I>>>()?;
+		Ok(List::new(&values))
+	}
+
+	/// Multiply the numbers by the key, mix them and add up the values after the
+	/// anchor. Fails if any number doesn't fit into `I`.
+	pub fn decrypt<I: Int>(&self, input: &[T]) -> anyhow::Result<I> {
+		let mut list = self.list(input)?;
+		for _ in 0 .. self.rounds {
+			list.mix();
+		}
+		let anchor = multiply(self.anchor, convert(self.key)?)?;
+		list.sum(anchor, &self.offsets)
+	}
+}
+
+fn convert<I: Int>(value: i128) -> anyhow::Result<I> {
+	I::from(value).with_context(|| {
+		format!("{value} doesn't fit into {}", std::any::type_name::<I>())
+	})
+}
+
+fn multiply<I: Int>(value: i128, key: I) -> anyhow::Result<I> {
+	convert::<I>(value)?.checked_mul(&key).with_context(|| {
+		format!(
+			"{value} * {key} doesn't fit into {}",
+			std::any::type_name::<I>()
+		)
+	})
+}
+
+/// The integer type to decrypt with.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum IntKind {
+	I32,
+	I64,
+	I128
+}
+
+impl IntKind {
+	pub fn decrypt(self, input: &[T], decryption: &Decryption) -> anyhow::Result<Answer> {
+		Ok(match self {
+			Self::I32 => decryption.decrypt::<i32>(input)?.into(),
+			Self::I64 => decryption.decrypt::<i64>(input)?.into(),
+			Self::I128 => decryption.decrypt::<i128>(input)?.into()
+		})
+	}
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+	type Input = Vec<T>;
+	type Params = ();
+
+	const EXAMPLE: () = ();
+	const REAL: () = ();
+
+	fn parse(input: &InputSource) -> anyhow::Result<Vec<T>> {
+		let input = read(input, parser())?;
+		info!("Input has {} values", input.len());
+		Ok(input)
+	}
+
+	fn part1(input: &Vec<T>, _: &()) -> anyhow::Result<Answer> {
+		Ok(Decryption::part1().decrypt::<T>(input)?.into())
+	}
+
+	fn part2(input: &Vec<T>, _: &()) -> anyhow::Result<Answer> {
+		Ok(Decryption::part2().decrypt::<T>(input)?.into())
+	}
+}
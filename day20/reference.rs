@@ -1,5 +1,9 @@
-use aoc22::read;
-use chumsky::prelude::*;
+//! The original implementation of [`List`], which moves every entry by swapping it with
+//! its neighbours one step at a time. It is kept around as a reference for testing the
+//! faster [`super::List`] against.
+
+use super::T;
+use log::{debug, trace};
 use std::{
 	cell::RefCell,
 	fmt::{self, Debug, Formatter},
@@ -7,28 +11,13 @@ use std::{
 	rc::Rc
 };
 
-type T = i64;
-
-fn parser() -> impl Parser<char, Vec<T>, Error = Simple<char>> {
-	let digit = one_of(['-', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9'])
-		.repeated()
-		.at_least(1)
-		.collect::<String>()
-		.map(|digits| digits.parse().unwrap());
-	digit
-		.then_ignore(one_of([' ', '\n']).repeated().at_least(1))
-		.repeated()
-		.at_least(1)
-		.then_ignore(end())
-}
-
 #[derive(Clone, Copy)]
 struct Entry {
 	idx: usize,
 	value: T
 }
 
-struct List {
+pub struct List {
 	q: Vec<Rc<RefCell<Entry>>>,
 	list: Vec<Rc<RefCell<Entry>>>
 }
@@ -59,7 +48,7 @@ impl Mul<T> for List {
 }
 
 impl List {
-	fn new(input: &[T]) -> Self {
+	pub fn new(input: &[T]) -> Self {
 		let mut q = Vec::new();
 		let mut list = Vec::new();
 		for (idx, value) in input.iter().enumerate() {
@@ -70,12 +59,12 @@ impl List {
 		Self { q, list }
 	}
 
-	fn mix(&mut self) {
-		println!("mixing ...");
+	pub fn mix(&mut self) {
+		debug!("mixing ...");
 		for entry in &self.q {
 			let entry: Entry = *entry.borrow();
 
-			// println!("{} moves:", entry.value);
+			trace!("{} moves:", entry.value);
 			let value = entry.value % (self.list.len() as i64 - 1);
 			if value > 0 {
 				for i in entry.idx .. entry.idx + value as usize {
@@ -96,11 +85,26 @@ impl List {
 				}
 			}
 
-			// println!("{:?}", self);
+			trace!("{self:?}");
 		}
 	}
 
-	fn sum(&self) -> T {
+	/// The original indices of all entries in the circular order they are in now,
+	/// starting with the first entry of the input.
+	pub fn order(&self) -> Vec<usize> {
+		let mut order = vec![0; self.q.len()];
+		for (original, entry) in self.q.iter().enumerate() {
+			order[entry.borrow().idx] = original;
+		}
+		let start = order
+			.iter()
+			.position(|original| *original == 0)
+			.unwrap_or(0);
+		order.rotate_left(start);
+		order
+	}
+
+	pub fn sum(&self) -> T {
 		let mut sum = 0;
 		let idx = self
 			.list
@@ -114,24 +118,3 @@ impl List {
 		sum
 	}
 }
-
-fn main() -> anyhow::Result<()> {
-	let input = read("input.txt", parser())?;
-	println!("Input has {} values", input.len());
-
-	// part 1
-
-	let mut list = List::new(&input);
-	list.mix();
-	println!("{}", list.sum());
-
-	// part 2
-
-	let mut list = List::new(&input) * 811589153;
-	for _ in 0 .. 10 {
-		list.mix()
-	}
-	println!("{}", list.sum());
-
-	Ok(())
-}
@@ -0,0 +1,122 @@
+use super::Int;
+use crate::{lines, read, InputSource};
+use anyhow::bail;
+use chumsky::{prelude::*, text::digits};
+use std::fmt::{self, Display, Formatter};
+
+/// A value that was taken out of the list at one position and put back at another.
+/// Positions are counted in the whole list, from its start.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Move<I> {
+	pub value: I,
+	pub from: usize,
+	pub to: usize
+}
+
+impl<I: Display> Display for Move<I> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "{} {} {}", self.value, self.from, self.to)
+	}
+}
+
+/// Every move made while mixing, written one `value from to` per line.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Trace<I> {
+	pub moves: Vec<Move<I>>
+}
+
+impl<I: Display> Display for Trace<I> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		for mv in &self.moves {
+			writeln!(f, "{mv}")?;
+		}
+		Ok(())
+	}
+}
+
+fn parser<I: Int>() -> impl Parser<char, Vec<Move<I>>, Error = Simple<char>> {
+	let value = just("-").or_not().then(digits(10)).try_map(
+		|(sign, digits): (Option<&str>, String), span| {
+			let text = format!("{}{digits}", sign.unwrap_or(""));
+			I::from_str_radix(&text, 10)
+				.map_err(|_| Simple::custom(span, format!("Invalid number: {text}")))
+		}
+	);
+	let pos = digits(10).try_map(|digits: String, span| {
+		digits
+			.parse()
+			.map_err(|_| Simple::custom(span, format!("Invalid position: {digits}")))
+	});
+	lines(
+		value
+			.then_ignore(just(" "))
+			.then(pos)
+			.then_ignore(just(" "))
+			.then(pos)
+			.map(|((value, from), to)| Move { value, from, to })
+	)
+	.then_ignore(end())
+}
+
+impl<I: Int> Trace<I> {
+	pub fn parse(input: &InputSource) -> anyhow::Result<Self> {
+		let moves = read(input, parser())?;
+		Ok(Self { moves })
+	}
+
+	/// Make the `i`-th move on a plain list of values, checking that it picks up the
+	/// value it claims to.
+	fn step(&self, list: &mut Vec<I>, i: usize) -> anyhow::Result<()> {
+		let mv = self.moves[i];
+		if mv.from >= list.len() || mv.to >= list.len() {
+			bail!("Move {} ({mv}) is out of bounds", i + 1);
+		}
+		if list[mv.from] != mv.value {
+			bail!(
+				"Move {} ({mv}) should move {}, but found {} at {}",
+				i + 1,
+				mv.value,
+				list[mv.from],
+				mv.from
+			);
+		}
+		let value = list.remove(mv.from);
+		list.insert(mv.to, value);
+		Ok(())
+	}
+
+	/// Make the moves on a plain list of values. Returns the list after every move.
+	pub fn replay(&self, values: &[I]) -> anyhow::Result<Vec<Vec<I>>> {
+		let mut list = values.to_vec();
+		let mut arrangements = Vec::new();
+		for i in 0 .. self.moves.len() {
+			self.step(&mut list, i)?;
+			arrangements.push(list.clone());
+		}
+		Ok(arrangements)
+	}
+
+	/// Make the moves on a plain list of values, and check that the list looks like
+	/// expected after every move.
+	pub fn verify(&self, values: &[I], expected: &[Vec<I>]) -> anyhow::Result<()> {
+		if self.moves.len() != expected.len() {
+			bail!(
+				"The trace has {} moves, but {} were expected",
+				self.moves.len(),
+				expected.len()
+			);
+		}
+		let mut list = values.to_vec();
+		for (i, expected) in expected.iter().enumerate() {
+			self.step(&mut list, i)?;
+			if list != *expected {
+				bail!(
+					"After move {} ({}), the list is {list:?} instead of {expected:?}",
+					i + 1,
+					self.moves[i]
+				);
+			}
+		}
+		Ok(())
+	}
+}
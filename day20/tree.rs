@@ -0,0 +1,180 @@
+//! An implicit treap: a sequence stored as a binary tree, ordered by position and
+//! balanced by random priorities. Every node knows the size of its subtree and its
+//! parent, so that the position of a node, the node at a position and moving a node
+//! all take `O(log n)` expected time.
+
+struct Node {
+	priority: u64,
+	/// The number of nodes in the subtree of this node, including itself.
+	size: usize,
+	left: Option<usize>,
+	right: Option<usize>,
+	parent: Option<usize>
+}
+
+/// A permutation of the nodes `0 .. len`. Nodes are never added or removed, only moved.
+pub struct Tree {
+	nodes: Vec<Node>,
+	root: Option<usize>
+}
+
+/// A pseudo-random priority for a node, so that the tree is balanced without needing
+/// a random number generator.
+fn priority(node: usize) -> u64 {
+	// splitmix64
+	let mut z = (node as u64).wrapping_add(0x9e3779b97f4a7c15);
+	z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+	z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+	z ^ (z >> 31)
+}
+
+impl Tree {
+	/// Create a tree with the nodes `0 .. len` in order.
+	pub fn new(len: usize) -> Self {
+		let mut tree = Self {
+			nodes: (0 .. len)
+				.map(|node| Node {
+					priority: priority(node),
+					size: 1,
+					left: None,
+					right: None,
+					parent: None
+				})
+				.collect(),
+			root: None
+		};
+		for node in 0 .. len {
+			let root = tree.merge(tree.root, Some(node));
+			tree.root = tree.detach(root);
+		}
+		tree
+	}
+
+	pub fn len(&self) -> usize {
+		self.nodes.len()
+	}
+
+	fn size(&self, node: Option<usize>) -> usize {
+		node.map_or(0, |node| self.nodes[node].size)
+	}
+
+	/// Recalculate the size of a node after its children changed, and make sure they
+	/// know their parent.
+	fn update(&mut self, node: usize) {
+		let Node { left, right, .. } = self.nodes[node];
+		self.nodes[node].size = self.size(left) + 1 + self.size(right);
+		for child in [left, right].into_iter().flatten() {
+			self.nodes[child].parent = Some(node);
+		}
+	}
+
+	/// Turn a subtree into a tree of its own.
+	fn detach(&mut self, node: Option<usize>) -> Option<usize> {
+		if let Some(node) = node {
+			self.nodes[node].parent = None;
+		}
+		node
+	}
+
+	/// Split a subtree into its first `k` nodes and the rest.
+	fn split(&mut self, node: Option<usize>, k: usize) -> (Option<usize>, Option<usize>) {
+		let Some(node) = node else {
+			return (None, None);
+		};
+		let Node { left, right, .. } = self.nodes[node];
+		let left_size = self.size(left);
+		if k <= left_size {
+			let (first, rest) = self.split(left, k);
+			self.nodes[node].left = rest;
+			self.update(node);
+			(first, Some(node))
+		} else {
+			let (first, rest) = self.split(right, k - left_size - 1);
+			self.nodes[node].right = first;
+			self.update(node);
+			(Some(node), rest)
+		}
+	}
+
+	/// Append one subtree to another.
+	fn merge(&mut self, first: Option<usize>, second: Option<usize>) -> Option<usize> {
+		let (first, second) = match (first, second) {
+			(None, node) | (node, None) => return node,
+			(Some(first), Some(second)) => (first, second)
+		};
+		if self.nodes[first].priority > self.nodes[second].priority {
+			let right = self.nodes[first].right;
+			self.nodes[first].right = self.merge(right, Some(second));
+			self.update(first);
+			Some(first)
+		} else {
+			let left = self.nodes[second].left;
+			self.nodes[second].left = self.merge(Some(first), left);
+			self.update(second);
+			Some(second)
+		}
+	}
+
+	/// The position of a node.
+	pub fn rank(&self, mut node: usize) -> usize {
+		let mut rank = self.size(self.nodes[node].left);
+		while let Some(parent) = self.nodes[node].parent {
+			if self.nodes[parent].right == Some(node) {
+				rank += self.size(self.nodes[parent].left) + 1;
+			}
+			node = parent;
+		}
+		rank
+	}
+
+	/// The node at a position.
+	pub fn at(&self, mut rank: usize) -> usize {
+		let mut node = self.root.expect("The tree is empty");
+		loop {
+			let Node { left, right, .. } = self.nodes[node];
+			let left_size = self.size(left);
+			node = match rank {
+				rank if rank < left_size => left.unwrap(),
+				rank if rank == left_size => return node,
+				_ => {
+					rank -= left_size + 1;
+					right.expect("Position out of bounds")
+				}
+			};
+		}
+	}
+
+	/// Take a node out of the sequence and insert it again at a position of the
+	/// remaining nodes.
+	pub fn move_to(&mut self, node: usize, rank: usize) {
+		let (before, rest) = self.split(self.root, self.rank(node));
+		let (_, after) = self.split(rest, 1);
+		let rest = self.merge(before, after);
+		let rest = self.detach(rest);
+
+		let (before, after) = self.split(rest, rank);
+		let node = self.detach(Some(node));
+		let before = self.merge(before, node);
+		let root = self.merge(before, after);
+		self.root = self.detach(root);
+	}
+
+	/// All nodes in order.
+	pub fn order(&self) -> Vec<usize> {
+		let mut order = Vec::with_capacity(self.len());
+		let mut stack = Vec::new();
+		let mut next = self.root;
+		loop {
+			while let Some(node) = next {
+				stack.push(node);
+				next = self.nodes[node].left;
+			}
+			let Some(node) = stack.pop() else {
+				break;
+			};
+			order.push(node);
+			next = self.nodes[node].right;
+		}
+		order
+	}
+}
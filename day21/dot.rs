@@ -0,0 +1,146 @@
+use super::{fold_known, graph, Expr};
+use anyhow::bail;
+use std::{collections::HashMap, fmt::Write as _};
+
+/// How nodes and edges on the path to the unknown are drawn.
+const HIGHLIGHT: &str = "color=red, fontcolor=red, penwidth=2";
+
+/// How everything else is drawn.
+const NORMAL: &str = "color=black";
+
+/// How values are drawn that could be calculated.
+const COLLAPSED: &str = "style=filled, fillcolor=lightgray";
+
+/// Quote a string as a DOT identifier.
+fn quote(text: &str) -> String {
+	format!("\"{}\"", text.replace('"', "\\\""))
+}
+
+fn attrs(highlight: bool) -> &'static str {
+	match highlight {
+		true => HIGHLIGHT,
+		false => NORMAL
+	}
+}
+
+/// Draw the expressions from [`partially_evaluate`](super::partially_evaluate) as a
+/// single tree in the DOT language, with the path to the unknown highlighted. Every
+/// monkey is drawn once, and its expression is linked to wherever it is used, so that
+/// long chains of monkeys are never built into one deep expression.
+pub fn expr_to_dot(exprs: &[(String, Expr)], unknown: &str) -> String {
+	let mut dot = String::from("digraph expr {\n\tnode [shape=box];\n");
+	let mut next = 0;
+	let mut roots = HashMap::new();
+	for (monkey, expr) in exprs.iter().rev() {
+		let root = expr_nodes(expr, unknown, &roots, &mut next, &mut dot);
+		roots.insert(monkey.as_str(), root);
+	}
+	dot.push_str("}\n");
+	dot
+}
+
+/// Add the nodes of an expression, and return the id of its node and whether it
+/// contains the unknown. Monkeys in `roots` are linked to instead of drawn again.
+fn expr_nodes(
+	expr: &Expr,
+	unknown: &str,
+	roots: &HashMap<&str, (usize, bool)>,
+	next: &mut usize,
+	dot: &mut String
+) -> (usize, bool) {
+	if let Expr::Variable(var) = expr {
+		if let Some(root) = roots.get(var.as_str()) {
+			return *root;
+		}
+	}
+	let id = *next;
+	*next += 1;
+	let (label, children) = match expr {
+		Expr::Literal(lit) => (lit.to_string(), Vec::new()),
+		Expr::Variable(var) => (var.clone(), Vec::new()),
+		Expr::Op(lhs, op, rhs) => (op.to_string(), vec![lhs, rhs])
+	};
+	let mut on_path = matches!(expr, Expr::Variable(var) if var == unknown);
+	for child in children {
+		let (child, child_on_path) = expr_nodes(child, unknown, roots, next, dot);
+		writeln!(dot, "\tn{id} -> n{child} [{}];", attrs(child_on_path)).unwrap();
+		on_path |= child_on_path;
+	}
+	let style = match (on_path, expr) {
+		(true, _) => HIGHLIGHT,
+		(false, Expr::Literal(_)) => COLLAPSED,
+		(false, _) => NORMAL
+	};
+	writeln!(dot, "\tn{id} [label={}, {style}];", quote(&label)).unwrap();
+	(id, on_path)
+}
+
+/// Draw the monkeys that the root waits for in the DOT language. The path from the
+/// root to the unknown is highlighted, and monkeys that don't wait for the unknown are
+/// collapsed into their values.
+pub fn monkeys_to_dot(
+	input: &HashMap<String, Expr>,
+	root: &str,
+	unknown: &str
+) -> anyhow::Result<String> {
+	for monkey in [root, unknown] {
+		if !input.contains_key(monkey) {
+			bail!("There is no monkey named {monkey}");
+		}
+	}
+	let folded = fold_known(input, root, unknown)?;
+	let on_path = |monkey: &str| match folded.get(monkey) {
+		Some(Expr::Literal(_)) => false,
+		Some(_) => true,
+		None => monkey == unknown
+	};
+	let sorted = graph::sort([root], |monkey| {
+		match (on_path(monkey), input.get(monkey)) {
+			(true, Some(expr)) if monkey != unknown => expr.variables(),
+			_ => Vec::new()
+		}
+	});
+
+	let mut dot = String::from("digraph monkeys {\n\tnode [shape=box];\n");
+	for monkey in sorted.order.into_iter().rev() {
+		let label = match (folded.get(monkey), input.get(monkey)) {
+			(Some(Expr::Literal(value)), _) => format!("{monkey}\\n= {value}"),
+			(_, Some(expr @ Expr::Op(_, op, _))) if op.is_function() => {
+				format!("{monkey}\\n{expr}")
+			},
+			(_, Some(Expr::Op(lhs, op, rhs))) if monkey != unknown => {
+				format!("{monkey}\\n{lhs} {op} {rhs}")
+			},
+			_ => monkey.to_owned()
+		};
+		let style = match on_path(monkey) {
+			true => HIGHLIGHT,
+			false => COLLAPSED
+		};
+		writeln!(
+			dot,
+			"\t{} [label={}, {style}];",
+			quote(monkey),
+			quote(&label)
+		)
+		.unwrap();
+		if !on_path(monkey) || monkey == unknown {
+			continue;
+		}
+		let Some(expr) = input.get(monkey) else {
+			continue;
+		};
+		for var in expr.variables() {
+			writeln!(
+				dot,
+				"\t{} -> {} [{}];",
+				quote(monkey),
+				quote(var),
+				attrs(on_path(var))
+			)
+			.unwrap();
+		}
+	}
+	dot.push_str("}\n");
+	Ok(dot)
+}
@@ -0,0 +1,141 @@
+use super::Monkey;
+use crate::{Diagnostic, Label};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Visit {
+	/// The monkey is on the stack, so we are still sorting the monkeys it waits for.
+	OnStack,
+	Done
+}
+
+/// The monkeys in topological order, and the cycles that prevented some of them from
+/// being sorted.
+pub(super) struct Sorted<'a> {
+	/// Every monkey comes after all the monkeys it waits for, unless they are part of a
+	/// cycle.
+	pub(super) order: Vec<&'a str>,
+	/// Monkeys that wait for each other, each waiting for the next one and the last one
+	/// waiting for the first one.
+	pub(super) cycles: Vec<Vec<&'a str>>
+}
+
+/// Sort all monkeys reachable from the roots topologically, without recursion.
+pub(super) fn sort<'a, R, F>(roots: R, waits_for: F) -> Sorted<'a>
+where
+	R: IntoIterator<Item = &'a str>,
+	F: Fn(&'a str) -> Vec<&'a str>
+{
+	let mut visits = HashMap::new();
+	let mut order = Vec::new();
+	let mut cycles = Vec::new();
+	for root in roots {
+		if visits.contains_key(root) {
+			continue;
+		}
+		visits.insert(root, Visit::OnStack);
+		let mut stack = vec![(root, waits_for(root), 0)];
+		while let Some((monkey, deps, next)) = stack.last_mut() {
+			let Some(&dep) = deps.get(*next) else {
+				visits.insert(*monkey, Visit::Done);
+				order.push(*monkey);
+				stack.pop();
+				continue;
+			};
+			*next += 1;
+			match visits.get(dep) {
+				None => {
+					visits.insert(dep, Visit::OnStack);
+					stack.push((dep, waits_for(dep), 0));
+				},
+				Some(Visit::OnStack) => {
+					let start = stack
+						.iter()
+						.position(|(monkey, ..)| *monkey == dep)
+						.unwrap();
+					cycles.push(
+						stack[start ..].iter().map(|(monkey, ..)| *monkey).collect()
+					);
+				},
+				Some(Visit::Done) => {}
+			}
+		}
+	}
+	Sorted { order, cycles }
+}
+
+/// Check that every monkey is defined exactly once, that every monkey they wait for
+/// exists, and that no monkeys wait for each other.
+pub(super) fn validate(text: &str, monkeys: &[Monkey]) -> Vec<Diagnostic> {
+	let mut diagnostics = Vec::new();
+
+	let mut by_name: HashMap<&str, &Monkey> = HashMap::new();
+	for monkey in monkeys {
+		if let Some(first) = by_name.get(monkey.name.as_str()) {
+			diagnostics.push(Diagnostic::custom(
+				text,
+				format!("Monkey {} is defined twice", monkey.name),
+				vec![
+					Label {
+						span: first.span.clone(),
+						message: "First defined here".to_owned()
+					},
+					Label {
+						span: monkey.span.clone(),
+						message: "Defined again here".to_owned()
+					},
+				]
+			));
+			continue;
+		}
+		by_name.insert(&monkey.name, monkey);
+	}
+
+	for monkey in monkeys {
+		for (name, span) in &monkey.refs {
+			if !by_name.contains_key(name.as_str()) {
+				diagnostics.push(Diagnostic::custom(
+					text,
+					format!("Monkey {} waits for an unknown monkey", monkey.name),
+					vec![Label {
+						span: span.clone(),
+						message: format!("There is no monkey named {name}")
+					}]
+				));
+			}
+		}
+	}
+
+	let sorted = sort(monkeys.iter().map(|monkey| monkey.name.as_str()), |name| {
+		by_name[name]
+			.refs
+			.iter()
+			.map(|(name, _)| name.as_str())
+			.filter(|name| by_name.contains_key(name))
+			.collect()
+	});
+	for cycle in sorted.cycles {
+		let labels = cycle
+			.iter()
+			.zip(cycle.iter().cycle().skip(1))
+			.map(|(monkey, next)| {
+				let (_, span) = by_name[monkey]
+					.refs
+					.iter()
+					.find(|(name, _)| name == next)
+					.unwrap();
+				Label {
+					span: span.clone(),
+					message: format!("{monkey} waits for {next}")
+				}
+			})
+			.collect();
+		let message = match cycle.as_slice() {
+			[monkey] => format!("Monkey {monkey} waits for itself"),
+			cycle => format!("Monkeys {} wait for each other", cycle.join(", "))
+		};
+		diagnostics.push(Diagnostic::custom(text, message, labels));
+	}
+
+	diagnostics
+}
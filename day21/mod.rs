@@ -0,0 +1,422 @@
+pub mod dot;
+mod graph;
+pub mod repl;
+pub mod symbolic;
+
+use crate::{lines, read_validated, Answer, InputSource, Solution};
+use anyhow::{bail, Context as _};
+use chumsky::{
+	prelude::*,
+	text::{digits, ident}
+};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One as _, ToPrimitive as _, Zero as _};
+use std::{
+	collections::HashMap,
+	fmt::{self, Display, Formatter},
+	ops::Range
+};
+use symbolic::Solutions;
+
+/// The numbers the monkeys yell. They are exact fractions, so that divisions never
+/// round and large numbers never overflow.
+pub type Number = BigRational;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Op {
+	Add,
+	Sub,
+	Mul,
+	Div,
+	/// The remainder of a division, with the sign of the divisor.
+	Mod,
+	Min,
+	Max,
+	/// The comparisons yell 1 if they hold and 0 otherwise.
+	Lt,
+	Le,
+	Gt,
+	Ge,
+	Eq,
+	Ne
+}
+
+impl Display for Op {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::Add => "+",
+			Self::Sub => "-",
+			Self::Mul => "*",
+			Self::Div => "/",
+			Self::Mod => "%",
+			Self::Min => "min",
+			Self::Max => "max",
+			Self::Lt => "<",
+			Self::Le => "<=",
+			Self::Gt => ">",
+			Self::Ge => ">=",
+			Self::Eq => "==",
+			Self::Ne => "!="
+		})
+	}
+}
+
+impl Op {
+	fn apply(self, lhs: &Number, rhs: &Number) -> anyhow::Result<Number> {
+		let truth = |holds| match holds {
+			true => Number::one(),
+			false => Number::zero()
+		};
+		Ok(match self {
+			Self::Add => lhs + rhs,
+			Self::Sub => lhs - rhs,
+			Self::Mul => lhs * rhs,
+			Self::Div | Self::Mod if rhs.is_zero() => {
+				bail!("{lhs} {self} {rhs} divides by zero")
+			},
+			Self::Div => lhs / rhs,
+			Self::Mod => lhs - rhs * (lhs / rhs).floor(),
+			Self::Min => lhs.min(rhs).clone(),
+			Self::Max => lhs.max(rhs).clone(),
+			Self::Lt => truth(lhs < rhs),
+			Self::Le => truth(lhs <= rhs),
+			Self::Gt => truth(lhs > rhs),
+			Self::Ge => truth(lhs >= rhs),
+			Self::Eq => truth(lhs == rhs),
+			Self::Ne => truth(lhs != rhs)
+		})
+	}
+
+	/// Whether this op is written like a function, as in `min(aaaa, bbbb)`.
+	pub fn is_function(self) -> bool {
+		matches!(self, Self::Min | Self::Max)
+	}
+
+	/// Whether we can solve for an unknown in one of the operands if we know the
+	/// result and the other operand. `%` forgets the quotient, `min` and `max` forget
+	/// one operand and the comparisons only remember a single bit, so none of them
+	/// can be inverted.
+	pub fn is_invertible(self) -> bool {
+		matches!(self, Self::Add | Self::Sub | Self::Mul | Self::Div)
+	}
+}
+
+#[derive(Clone, Debug)]
+pub enum Expr {
+	Literal(Number),
+	Variable(String),
+	Op(Box<Expr>, Op, Box<Expr>)
+}
+
+impl Display for Expr {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Literal(lit) => write!(f, "{lit}"),
+			Self::Variable(var) => write!(f, "{var}"),
+			Self::Op(lhs, op, rhs) if op.is_function() => write!(f, "{op}({lhs}, {rhs})"),
+			Self::Op(lhs, op, rhs) => write!(f, "({lhs} {op} {rhs})")
+		}
+	}
+}
+
+/// An expression, together with the names and spans of all monkeys it refers to.
+type Parsed = (Expr, Vec<(String, Span)>);
+
+/// Parse operands separated by any of the ops, from left to right.
+fn binary<P, O>(
+	operand: P,
+	ops: O
+) -> impl Parser<char, Parsed, Error = Simple<char>> + Clone
+where
+	P: Parser<char, Parsed, Error = Simple<char>> + Clone,
+	O: Parser<char, Op, Error = Simple<char>> + Clone
+{
+	let space = just(' ').repeated();
+	operand
+		.clone()
+		.then(
+			space
+				.ignore_then(ops)
+				.then_ignore(space)
+				.then(operand)
+				.repeated()
+		)
+		.foldl(|lhs, (op, rhs)| Expr::combine(lhs, op, rhs))
+}
+
+impl Expr {
+	fn combine((lhs, mut refs): Parsed, op: Op, (rhs, rhs_refs): Parsed) -> Parsed {
+		refs.extend(rhs_refs);
+		(Self::Op(Box::new(lhs), op, Box::new(rhs)), refs)
+	}
+
+	/// Parse an expression, together with the names and spans of all monkeys it refers
+	/// to. Comparisons bind weaker than `+` and `-`, which bind weaker than `*`, `/`
+	/// and `%`.
+	fn parser() -> impl Parser<char, Parsed, Error = Simple<char>> + Clone {
+		recursive(|expr| {
+			let space = just(' ').repeated();
+			let lit = digits(10).map(|digits: String| {
+				let lit = Self::Literal(Number::from_integer(digits.parse().unwrap()));
+				(lit, Vec::new())
+			});
+			let var = ident().map_with_span(|ident: String, span| {
+				(Self::Variable(ident.clone()), vec![(ident, span)])
+			});
+			let args = expr
+				.clone()
+				.then_ignore(space)
+				.then_ignore(just(","))
+				.then_ignore(space)
+				.then(expr.clone())
+				.delimited_by(just("("), just(")"));
+			let call =
+				choice((just("min").map(|_| Op::Min), just("max").map(|_| Op::Max)))
+					.then(args)
+					.map(|(op, (lhs, rhs))| Self::combine(lhs, op, rhs));
+			let atom = choice((
+				lit,
+				call,
+				var,
+				expr.delimited_by(just("(").then(space), space.then(just(")")))
+			));
+
+			let product = binary(
+				atom,
+				choice((
+					just("*").map(|_| Op::Mul),
+					just("/").map(|_| Op::Div),
+					just("%").map(|_| Op::Mod)
+				))
+			);
+			let sum = binary(
+				product,
+				choice((just("+").map(|_| Op::Add), just("-").map(|_| Op::Sub)))
+			);
+			binary(
+				sum,
+				choice((
+					just("<=").map(|_| Op::Le),
+					just(">=").map(|_| Op::Ge),
+					just("==").map(|_| Op::Eq),
+					just("!=").map(|_| Op::Ne),
+					just("<").map(|_| Op::Lt),
+					just(">").map(|_| Op::Gt)
+				))
+			)
+		})
+	}
+
+	/// The names of all monkeys this expression refers to.
+	fn variables(&self) -> Vec<&str> {
+		match self {
+			Self::Literal(_) => Vec::new(),
+			Self::Variable(var) => vec![var.as_str()],
+			Self::Op(lhs, _, rhs) => {
+				let mut vars = lhs.variables();
+				vars.extend(rhs.variables());
+				vars
+			}
+		}
+	}
+
+	/// Fill in the values of all monkeys that are known, and calculate everything that
+	/// can be calculated. Monkeys that are only partially known are kept as variables
+	/// rather than inlined, so that folding a long chain of monkeys takes linear time.
+	fn fold(&self, exprs: &HashMap<String, Expr>) -> anyhow::Result<Self> {
+		Ok(match self {
+			Self::Literal(lit) => Self::Literal(lit.clone()),
+			Self::Variable(var) => match exprs.get(var) {
+				Some(Self::Literal(lit)) => Self::Literal(lit.clone()),
+				_ => Self::Variable(var.clone())
+			},
+			Self::Op(lhs, op, rhs) => match (lhs.fold(exprs)?, rhs.fold(exprs)?) {
+				(Self::Literal(lhs), Self::Literal(rhs)) => {
+					Self::Literal(op.apply(&lhs, &rhs)?)
+				},
+				(lhs, rhs) => Self::Op(Box::new(lhs), *op, Box::new(rhs))
+			}
+		})
+	}
+}
+
+type Span = Range<usize>;
+
+/// A line of the input.
+struct Monkey {
+	name: String,
+	span: Span,
+	expr: Expr,
+	/// The monkeys this monkey waits for, and where they appear in the input.
+	refs: Vec<(String, Span)>
+}
+
+fn parser() -> impl Parser<char, Vec<Monkey>, Error = Simple<char>> {
+	lines(
+		ident()
+			.map_with_span(|name: String, span| (name, span))
+			.then_ignore(just(": "))
+			.then(Expr::parser())
+			.map(|((name, span), (expr, refs))| Monkey {
+				name,
+				span,
+				expr,
+				refs
+			})
+	)
+	.then_ignore(end())
+}
+
+/// Evaluate a monkey and all the monkeys it waits for, in topological order. Every
+/// monkey on the way is replaced by its value if it is known, or by its expression
+/// with everything that is known filled in otherwise.
+fn evaluate(
+	name: &str,
+	exprs: &mut HashMap<String, Expr>
+) -> anyhow::Result<Option<Number>> {
+	let sorted = graph::sort([name], |monkey| match exprs.get(monkey) {
+		Some(expr) => expr.variables(),
+		None => Vec::new()
+	});
+	if let Some(cycle) = sorted.cycles.first() {
+		bail!("Monkeys {} wait for each other", cycle.join(", "));
+	}
+	let order: Vec<String> = sorted.order.into_iter().map(str::to_owned).collect();
+	for monkey in order {
+		if let Some(expr) = exprs.get(&monkey) {
+			let expr = expr.fold(exprs)?;
+			exprs.insert(monkey, expr);
+		}
+	}
+	Ok(match exprs.get(name) {
+		Some(Expr::Literal(value)) => Some(value.clone()),
+		_ => None
+	})
+}
+
+/// Evaluate all monkeys an expression refers to, then the expression itself.
+fn evaluate_expr(expr: &Expr, exprs: &mut HashMap<String, Expr>) -> anyhow::Result<Expr> {
+	for var in expr.variables() {
+		evaluate(var, exprs)?;
+	}
+	expr.fold(exprs)
+}
+
+/// Evaluate everything that doesn't depend on the unknown. Afterwards, every monkey
+/// that doesn't wait for the unknown is a literal, and every other monkey is its own
+/// expression with the values of the monkeys it waits for filled in.
+fn fold_known(
+	input: &HashMap<String, Expr>,
+	name: &str,
+	unknown: &str
+) -> anyhow::Result<HashMap<String, Expr>> {
+	let mut exprs = input.clone();
+	exprs.remove(unknown);
+	evaluate(name, &mut exprs)?;
+	Ok(exprs)
+}
+
+/// The expressions of the monkeys on the way from a monkey to the unknown, with
+/// everything that doesn't depend on the unknown calculated. The monkey itself comes
+/// first, and every expression refers to the monkeys after it by name.
+pub fn partially_evaluate(
+	input: &HashMap<String, Expr>,
+	name: &str,
+	unknown: &str
+) -> anyhow::Result<Vec<(String, Expr)>> {
+	if name == unknown {
+		return Ok(vec![(name.to_owned(), Expr::Variable(name.to_owned()))]);
+	}
+	let mut folded = fold_known(input, name, unknown)?;
+	if !folded.contains_key(name) {
+		bail!("There is no monkey named {name}");
+	}
+	let sorted = graph::sort([name], |monkey| match folded.get(monkey) {
+		Some(expr) => expr.variables(),
+		None => Vec::new()
+	});
+	let order: Vec<String> = sorted.order.into_iter().map(str::to_owned).collect();
+	Ok(order
+		.into_iter()
+		.rev()
+		.filter_map(|monkey| {
+			let expr = folded.remove(&monkey)?;
+			Some((monkey, expr))
+		})
+		.collect())
+}
+
+/// Substitute `value` for the unknown and check that both sides of the root monkey's
+/// equation are equal.
+pub fn verify(
+	input: &HashMap<String, Expr>,
+	unknown: &str,
+	value: &Number
+) -> anyhow::Result<()> {
+	let mut exprs = input.clone();
+	exprs.insert(unknown.to_owned(), Expr::Literal(value.clone()));
+	let Some(Expr::Op(lhs, _, rhs)) = exprs.get("root").cloned() else {
+		bail!("The root monkey needs to compare two values")
+	};
+	match (
+		evaluate_expr(&lhs, &mut exprs)?,
+		evaluate_expr(&rhs, &mut exprs)?
+	) {
+		(Expr::Literal(lhs), Expr::Literal(rhs)) if lhs == rhs => Ok(()),
+		(lhs, rhs) => {
+			bail!("With {unknown} = {value}, the root monkey compares {lhs} with {rhs}")
+		}
+	}
+}
+
+/// Turn a number into an answer, as long as it is an integer.
+fn answer(value: &Number, name: &str) -> anyhow::Result<Answer> {
+	if !value.is_integer() {
+		bail!("{name} is {value}, which is not an integer");
+	}
+	let value: BigInt = value.to_integer();
+	Ok(match value.to_i128() {
+		Some(value) => value.into(),
+		None => value.to_string().into()
+	})
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+	type Input = HashMap<String, Expr>;
+	type Params = ();
+
+	const EXAMPLE: () = ();
+	const REAL: () = ();
+
+	fn parse(input: &InputSource) -> anyhow::Result<HashMap<String, Expr>> {
+		let monkeys = read_validated(input, parser(), |text, monkeys| {
+			graph::validate(text, monkeys)
+		})?;
+		Ok(monkeys
+			.into_iter()
+			.map(|monkey| (monkey.name, monkey.expr))
+			.collect())
+	}
+
+	fn part1(input: &HashMap<String, Expr>, _: &()) -> anyhow::Result<Answer> {
+		let mut exprs = input.clone();
+		let root = evaluate("root", &mut exprs)?.context("Unable to evaluate root")?;
+		answer(&root, "root")
+	}
+
+	fn part2(input: &HashMap<String, Expr>, _: &()) -> anyhow::Result<Answer> {
+		let Some(Expr::Op(lhs, _, rhs)) = input.get("root") else {
+			bail!("The root monkey needs to compare two values")
+		};
+		match symbolic::solve(lhs, rhs, input, "humn")? {
+			Solutions::One(value) => {
+				verify(input, "humn", &value)?;
+				answer(&value, "humn")
+			},
+			solutions => bail!("Found {solutions} for humn")
+		}
+	}
+}
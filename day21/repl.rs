@@ -0,0 +1,173 @@
+use super::{
+	evaluate, graph, partially_evaluate,
+	symbolic::{self, Solutions},
+	Expr
+};
+use anyhow::{anyhow, bail};
+use chumsky::prelude::*;
+use std::{
+	collections::HashMap,
+	io::{BufRead, Write}
+};
+
+const HELP: &str = "\
+value NAME                 print the value of a monkey
+show NAME [UNKNOWN]        print the expressions of the monkeys on the way from a monkey to
+                           the unknown (default humn), with everything that doesn't depend
+                           on it calculated
+set NAME EXPR              replace the expression of a monkey, like `set humn 301`
+solve X [LHS = RHS]        find the value of X that makes both sides equal (default: the
+                           two sides of root)
+reset                      undo all changes made using `set`
+help                       print this help
+quit                       stop exploring
+";
+
+const UNKNOWN: &str = "humn";
+
+/// Explore the monkeys interactively.
+pub struct Repl {
+	input: HashMap<String, Expr>,
+	exprs: HashMap<String, Expr>
+}
+
+impl Repl {
+	pub fn new(input: HashMap<String, Expr>) -> Self {
+		Self {
+			exprs: input.clone(),
+			input
+		}
+	}
+
+	/// Read commands until the input ends or the user quits.
+	pub fn repl(
+		&mut self,
+		input: impl BufRead,
+		mut out: impl Write
+	) -> anyhow::Result<()> {
+		write!(out, "> ")?;
+		out.flush()?;
+		for line in input.lines() {
+			match self.command(&line?, &mut out) {
+				Ok(true) => {},
+				Ok(false) => break,
+				Err(err) => writeln!(out, "Error: {err}")?
+			}
+			write!(out, "> ")?;
+			out.flush()?;
+		}
+		writeln!(out)?;
+		Ok(())
+	}
+
+	/// Run a single command. Returns false if the user wants to quit.
+	pub fn command(&mut self, line: &str, out: &mut impl Write) -> anyhow::Result<bool> {
+		let line = line.trim();
+		if line.is_empty() {
+			return Ok(true);
+		}
+		let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+		let rest = rest.trim();
+		let args: Vec<&str> = rest.split_whitespace().collect();
+
+		match command {
+			"v" | "value" => {
+				let [name] = args[..] else {
+					bail!("Which monkey? Try `value root`");
+				};
+				self.monkey(name)?;
+				let mut exprs = self.exprs.clone();
+				match evaluate(name, &mut exprs)? {
+					Some(value) => writeln!(out, "{name} = {value}")?,
+					None => bail!("Unable to evaluate {name}")
+				}
+			},
+			"p" | "show" => {
+				let (name, unknown) = match args[..] {
+					[name] => (name, UNKNOWN),
+					[name, unknown] => (name, unknown),
+					_ => bail!("Which monkey? Try `show root`")
+				};
+				self.monkey(name)?;
+				for (monkey, expr) in partially_evaluate(&self.exprs, name, unknown)? {
+					writeln!(out, "{monkey} = {expr}")?;
+				}
+			},
+			"set" => {
+				let Some((name, expr)) = rest.split_once(' ') else {
+					bail!("Which monkey and expression? Try `set humn 301`");
+				};
+				self.monkey(name)?;
+				let expr = self.parse(expr)?;
+				self.set(name, expr)?;
+				writeln!(out, "{name}: {}", self.exprs[name])?;
+			},
+			"solve" => {
+				let Some(&unknown) = args.first() else {
+					bail!("Solve for which monkey? Try `solve humn`");
+				};
+				self.monkey(unknown)?;
+				let equation = rest[unknown.len() ..].trim();
+				let (lhs, rhs) = match equation.is_empty() {
+					true => match self.exprs.get("root") {
+						Some(Expr::Op(lhs, _, rhs)) => ((**lhs).clone(), (**rhs).clone()),
+						_ => bail!("The root monkey needs to compare two values")
+					},
+					false => {
+						let Some((lhs, rhs)) = equation.split_once(" = ") else {
+							bail!("Expected an equation like `aaaa = bbbb`");
+						};
+						(self.parse(lhs)?, self.parse(rhs)?)
+					}
+				};
+				match symbolic::solve(&lhs, &rhs, &self.exprs, unknown)? {
+					Solutions::One(value) => writeln!(out, "{unknown} = {value}")?,
+					solutions => writeln!(out, "Found {solutions} for {unknown}")?
+				}
+			},
+			"reset" => self.exprs = self.input.clone(),
+			"h" | "help" => write!(out, "{HELP}")?,
+			"q" | "quit" => return Ok(false),
+			_ => bail!("Unknown command {command:?}, try `help`")
+		}
+		Ok(true)
+	}
+
+	fn monkey(&self, name: &str) -> anyhow::Result<()> {
+		match self.exprs.contains_key(name) {
+			true => Ok(()),
+			false => bail!("There is no monkey named {name}")
+		}
+	}
+
+	/// Parse an expression that only refers to monkeys that exist.
+	fn parse(&self, text: &str) -> anyhow::Result<Expr> {
+		let (expr, refs) = Expr::parser()
+			.then_ignore(end())
+			.parse(text.trim())
+			.map_err(|errs| {
+				let column = errs[0].span().start + 1;
+				anyhow!("Unable to parse {:?} at column {column}", text.trim())
+			})?;
+		for (name, _) in refs {
+			self.monkey(&name)?;
+		}
+		Ok(expr)
+	}
+
+	/// Replace the expression of a monkey, unless it would make monkeys wait for each
+	/// other.
+	fn set(&mut self, name: &str, expr: Expr) -> anyhow::Result<()> {
+		let old = self.exprs.insert(name.to_owned(), expr).unwrap();
+		let sorted = graph::sort([name], |monkey| self.exprs[monkey].variables());
+		let err = match sorted.cycles.first().map(Vec::as_slice) {
+			None => return Ok(()),
+			Some([monkey]) => anyhow!("Monkey {monkey} would wait for itself"),
+			Some(cycle) => {
+				anyhow!("Monkeys {} would wait for each other", cycle.join(", "))
+			}
+		};
+		self.exprs.insert(name.to_owned(), old);
+		Err(err)
+	}
+}
@@ -0,0 +1,268 @@
+use super::{graph, Expr, Number, Op};
+use anyhow::{bail, Context as _};
+use num_traits::{One as _, Signed as _, Zero as _};
+use std::{
+	collections::HashMap,
+	fmt::{self, Display, Formatter}
+};
+
+/// `x * unknown + c`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Linear {
+	pub x: Number,
+	pub c: Number
+}
+
+impl Linear {
+	fn new(x: Number, c: Number) -> Self {
+		Self { x, c }
+	}
+
+	fn constant(c: Number) -> Self {
+		Self::new(Number::zero(), c)
+	}
+
+	fn is_zero(&self) -> bool {
+		self.x.is_zero() && self.c.is_zero()
+	}
+
+	fn add(&self, other: &Self) -> Self {
+		Self::new(&self.x + &other.x, &self.c + &other.c)
+	}
+
+	fn sub(&self, other: &Self) -> Self {
+		Self::new(&self.x - &other.x, &self.c - &other.c)
+	}
+
+	fn scale(&self, factor: &Number) -> Self {
+		Self::new(&self.x * factor, &self.c * factor)
+	}
+
+	/// The coefficients of `unknown^2`, `unknown` and the constant of the product.
+	fn product(&self, other: &Self) -> [Number; 3] {
+		[
+			&self.x * &other.x,
+			&self.x * &other.c + &self.c * &other.x,
+			&self.c * &other.c
+		]
+	}
+
+	fn mul(&self, other: &Self) -> anyhow::Result<Self> {
+		match self.product(other) {
+			[square, x, c] if square.is_zero() => Ok(Self::new(x, c)),
+			_ => bail!("({self}) * ({other}) is not linear anymore")
+		}
+	}
+
+	fn eval(&self, value: &Number) -> Number {
+		&self.x * value + &self.c
+	}
+}
+
+impl Display for Linear {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let coef = match &self.x {
+			x if x.is_one() => String::new(),
+			x if (-x).is_one() => "-".to_owned(),
+			x => x.to_string()
+		};
+		match &self.c {
+			c if self.x.is_zero() => write!(f, "{c}"),
+			c if c.is_zero() => write!(f, "{coef}x"),
+			c if c.is_negative() => write!(f, "{coef}x - {}", -c),
+			c => write!(f, "{coef}x + {c}")
+		}
+	}
+}
+
+/// `num / den`, where both the numerator and the denominator are linear in the
+/// unknown.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Form {
+	pub num: Linear,
+	pub den: Linear
+}
+
+impl Form {
+	fn constant(value: Number) -> Self {
+		Self {
+			num: Linear::constant(value),
+			den: Linear::constant(Number::one())
+		}
+	}
+
+	/// The value of the form, unless it depends on the unknown.
+	fn value(&self) -> Option<Number> {
+		match self.num.x.is_zero() && self.den.x.is_zero() {
+			true => Some(&self.num.c / &self.den.c),
+			false => None
+		}
+	}
+
+	fn unknown() -> Self {
+		Self {
+			num: Linear::new(Number::one(), Number::zero()),
+			den: Linear::constant(Number::one())
+		}
+	}
+
+	/// Make the leading coefficient of the denominator one.
+	fn reduce(self) -> Self {
+		let leading = match self.den.x.is_zero() {
+			true => &self.den.c,
+			false => &self.den.x
+		};
+		if leading.is_zero() || leading.is_one() {
+			return self;
+		}
+		let factor = leading.recip();
+		Self {
+			num: self.num.scale(&factor),
+			den: self.den.scale(&factor)
+		}
+	}
+
+	fn apply(&self, op: Op, rhs: &Self) -> anyhow::Result<Self> {
+		let lhs = self;
+		if !op.is_invertible() {
+			let (Some(lhs), Some(rhs)) = (lhs.value(), rhs.value()) else {
+				bail!("{op} can't be inverted, but ({lhs}) {op} ({rhs}) depends on the unknown");
+			};
+			return Ok(Self::constant(op.apply(&lhs, &rhs)?));
+		}
+		let form = match op {
+			Op::Add | Op::Sub if lhs.den == rhs.den => Self {
+				num: match op {
+					Op::Add => lhs.num.add(&rhs.num),
+					_ => lhs.num.sub(&rhs.num)
+				},
+				den: lhs.den.clone()
+			},
+			Op::Add => Self {
+				num: lhs.num.mul(&rhs.den)?.add(&rhs.num.mul(&lhs.den)?),
+				den: lhs.den.mul(&rhs.den)?
+			},
+			Op::Sub => Self {
+				num: lhs.num.mul(&rhs.den)?.sub(&rhs.num.mul(&lhs.den)?),
+				den: lhs.den.mul(&rhs.den)?
+			},
+			Op::Mul => Self {
+				num: lhs.num.mul(&rhs.num)?,
+				den: lhs.den.mul(&rhs.den)?
+			},
+			Op::Div => {
+				if rhs.num.is_zero() {
+					bail!("({lhs}) / ({rhs}) divides by zero");
+				}
+				Self {
+					num: lhs.num.mul(&rhs.den)?,
+					den: lhs.den.mul(&rhs.num)?
+				}
+			},
+			op => unreachable!("{op} was checked to be invertible")
+		};
+		Ok(form.reduce())
+	}
+}
+
+impl Display for Form {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		if self.den.x.is_zero() && self.den.c.is_one() {
+			write!(f, "{}", self.num)
+		} else {
+			write!(f, "({}) / ({})", self.num, self.den)
+		}
+	}
+}
+
+/// Simplify an expression into a [`Form`] in the unknown, given the forms of all
+/// monkeys it refers to.
+fn form(expr: &Expr, forms: &HashMap<&str, Form>) -> anyhow::Result<Form> {
+	match expr {
+		Expr::Literal(lit) => Ok(Form::constant(lit.clone())),
+		Expr::Variable(var) => Ok(forms[var.as_str()].clone()),
+		Expr::Op(lhs, op, rhs) => form(lhs, forms)?.apply(*op, &form(rhs, forms)?)
+	}
+}
+
+/// Simplify an expression into a [`Form`] in the unknown. The monkeys it refers to are
+/// simplified in topological order, without recursion.
+pub fn simplify(
+	expr: &Expr,
+	exprs: &HashMap<String, Expr>,
+	unknown: &str
+) -> anyhow::Result<Form> {
+	let sorted = graph::sort(expr.variables(), |monkey| match exprs.get(monkey) {
+		Some(expr) if monkey != unknown => expr.variables(),
+		_ => Vec::new()
+	});
+	if let Some(cycle) = sorted.cycles.first() {
+		bail!("Monkeys {} wait for each other", cycle.join(", "));
+	}
+
+	let mut forms = HashMap::new();
+	for monkey in sorted.order {
+		let form = match monkey == unknown {
+			true => Form::unknown(),
+			false => {
+				let expr = exprs
+					.get(monkey)
+					.with_context(|| format!("There is no monkey named {monkey}"))?;
+				form(expr, &forms)?
+			}
+		};
+		forms.insert(monkey, form);
+	}
+	form(expr, &forms)
+}
+
+/// The values of the unknown that solve an equation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Solutions {
+	None,
+	One(Number),
+	/// Every value works, except those that would divide by zero.
+	Infinite
+}
+
+impl Display for Solutions {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::None => f.write_str("no solution"),
+			Self::One(value) => write!(f, "exactly one solution: {value}"),
+			Self::Infinite => f.write_str("infinitely many solutions")
+		}
+	}
+}
+
+/// Solve `lhs = rhs` for the unknown.
+pub fn solve(
+	lhs: &Expr,
+	rhs: &Expr,
+	exprs: &HashMap<String, Expr>,
+	unknown: &str
+) -> anyhow::Result<Solutions> {
+	let lhs = simplify(lhs, exprs, unknown)?;
+	let rhs = simplify(rhs, exprs, unknown)?;
+
+	// lhs.num * rhs.den - rhs.num * lhs.den = 0
+	let [l2, l1, l0] = lhs.num.product(&rhs.den);
+	let [r2, r1, r0] = rhs.num.product(&lhs.den);
+	if l2 != r2 {
+		bail!("{lhs} = {rhs} is quadratic in {unknown}");
+	}
+	let eq = Linear::new(l1 - r1, l0 - r0);
+	Ok(if eq.x.is_zero() {
+		match eq.c.is_zero() {
+			true => Solutions::Infinite,
+			false => Solutions::None
+		}
+	} else {
+		let value = -eq.c / eq.x;
+		if lhs.den.eval(&value).is_zero() || rhs.den.eval(&value).is_zero() {
+			Solutions::None
+		} else {
+			Solutions::One(value)
+		}
+	})
+}
@@ -0,0 +1,234 @@
+use super::{Facing, Map, Tile};
+use crate::Point;
+use anyhow::{bail, Context as _};
+use log::debug;
+use std::{collections::VecDeque, ops::Neg};
+
+/// A unit vector in 3D space.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Vec3 {
+	x: i8,
+	y: i8,
+	z: i8
+}
+
+impl Vec3 {
+	const fn new(x: i8, y: i8, z: i8) -> Self {
+		Self { x, y, z }
+	}
+}
+
+impl Neg for Vec3 {
+	type Output = Self;
+
+	fn neg(self) -> Self {
+		Self::new(-self.x, -self.y, -self.z)
+	}
+}
+
+/// A face of the cube. The face is a square in the net, and we keep track of where it
+/// ended up after folding the net into a cube.
+#[derive(Debug)]
+pub(super) struct Face {
+	/// The top left tile of the face in the net.
+	pub(super) origin: Point,
+	/// The direction the face is pointing to, away from the cube.
+	normal: Vec3,
+	/// The direction you are going on the cube when facing right on this face.
+	right: Vec3,
+	/// The direction you are going on the cube when facing down on this face.
+	down: Vec3
+}
+
+impl Face {
+	/// The direction you are going on the cube when facing some way on this face.
+	fn dir(&self, facing: Facing) -> Vec3 {
+		match facing {
+			Facing::Right => self.right,
+			Facing::Down => self.down,
+			Facing::Left => -self.right,
+			Facing::Up => -self.down
+		}
+	}
+
+	/// The face you end up on when walking across the edge in the direction of `facing`.
+	/// This only works for faces that are next to each other in the net.
+	fn fold(&self, origin: Point, facing: Facing) -> Self {
+		let dir = self.dir(facing);
+		let (right, down) = match facing {
+			Facing::Right | Facing::Left => (-self.normal, self.down),
+			Facing::Down | Facing::Up => (self.right, -self.normal)
+		};
+		let (right, down) = match facing {
+			Facing::Right | Facing::Down => (right, down),
+			Facing::Left => (-right, down),
+			Facing::Up => (right, -down)
+		};
+		Self {
+			origin,
+			normal: dir,
+			right,
+			down
+		}
+	}
+}
+
+/// The net of a cube, folded up.
+#[derive(Debug)]
+pub struct Cube {
+	/// The length of an edge of the cube.
+	pub(super) size: i64,
+	pub(super) faces: Vec<Face>,
+	/// For every face and facing, the face across that edge and the facing after
+	/// walking across it.
+	edges: Vec<[(usize, Facing); 4]>
+}
+
+impl Cube {
+	/// Fold the map into a cube. This works for all 11 nets of a cube, in any size.
+	pub fn fold(map: &Map) -> anyhow::Result<Self> {
+		let tiles = map
+			.grid
+			.values()
+			.filter(|tile| **tile != Tile::Void)
+			.count();
+		let size = (tiles as f64 / 6.0).sqrt().round() as i64;
+		if size == 0 || size * size * 6 != tiles as i64 {
+			bail!("The map has {tiles} tiles, which can't be folded into a cube");
+		}
+
+		// find the faces in the net
+		let bounds = map.grid.bounds().context("The map is empty")?;
+		let mut net = Vec::new();
+		for y in (bounds.min.y ..= bounds.max.y).step_by(size as usize) {
+			for x in (bounds.min.x ..= bounds.max.x).step_by(size as usize) {
+				let origin = Point::new(x, y);
+				if map.tile(origin) != Tile::Void {
+					net.push(origin);
+				}
+			}
+		}
+		if net.len() != 6 {
+			bail!(
+				"Found {} faces of size {size} in the map, but a cube needs 6",
+				net.len()
+			);
+		}
+
+		// fold the net, starting with the first face
+		let mut faces: Vec<Option<Face>> = net.iter().map(|_| None).collect();
+		faces[0] = Some(Face {
+			origin: net[0],
+			normal: Vec3::new(0, 0, -1),
+			right: Vec3::new(1, 0, 0),
+			down: Vec3::new(0, 1, 0)
+		});
+		let mut q = VecDeque::from([0]);
+		while let Some(idx) = q.pop_front() {
+			for facing in Facing::ALL {
+				let origin = net[idx] + facing.dir() * size;
+				let Some(next) = net.iter().position(|face| *face == origin) else {
+					continue;
+				};
+				if faces[next].is_none() {
+					faces[next] = Some(faces[idx].as_ref().unwrap().fold(origin, facing));
+					q.push_back(next);
+				}
+			}
+		}
+		let faces = faces
+			.into_iter()
+			.collect::<Option<Vec<_>>>()
+			.context("The faces of the map are not connected")?;
+
+		// find the neighbours of each face
+		let mut edges = Vec::new();
+		for face in &faces {
+			let mut face_edges = [(0, Facing::Right); 4];
+			for facing in Facing::ALL {
+				let dir = face.dir(facing);
+				let next = faces
+					.iter()
+					.position(|face| face.normal == dir)
+					.context("Two faces of the map overlap when folded")?;
+				let next_facing = Facing::ALL
+					.into_iter()
+					.find(|f| faces[next].dir(*f) == -face.normal)
+					.unwrap();
+				face_edges[facing as usize] = (next, next_facing);
+			}
+			edges.push(face_edges);
+		}
+
+		Ok(Self { size, faces, edges })
+	}
+
+	/// The face that contains the point.
+	pub(super) fn face_at(&self, point: Point) -> Option<usize> {
+		self.faces.iter().position(|face| {
+			(face.origin.x .. face.origin.x + self.size).contains(&point.x)
+				&& (face.origin.y .. face.origin.y + self.size).contains(&point.y)
+		})
+	}
+
+	/// The face across the edge of `face` in the direction of `facing`, and the facing
+	/// after walking across that edge.
+	pub(super) fn neighbour(&self, face: usize, facing: Facing) -> (usize, Facing) {
+		self.edges[face][facing as usize]
+	}
+
+	/// Take a single step on the cube, ignoring walls. Returns the new position and
+	/// facing.
+	pub fn step(&self, pos: Point, facing: Facing) -> (Point, Facing) {
+		let next = pos + facing.dir();
+		// if we stay on the net, this is just like walking on the plane
+		if self.face_at(next).is_some() {
+			return (next, facing);
+		}
+
+		let face = self.face_at(pos).expect("Position is not on the cube");
+		let (next_face, next_facing) = self.neighbour(face, facing);
+		let offset = self.edge_offset(pos - self.faces[face].origin, facing);
+		// both faces go around the edge they share in opposite directions
+		let next_offset = self.size - 1 - offset;
+		// we enter the next face through the edge opposite of where we are heading
+		let next = self.faces[next_face].origin
+			+ self.edge_point(next_offset, next_facing.opposite());
+		(next, next_facing)
+	}
+
+	/// The offset of a point on the edge of a face in the direction of `facing`,
+	/// going clockwise around the face.
+	fn edge_offset(&self, local: Point, edge: Facing) -> i64 {
+		let n = self.size;
+		match edge {
+			Facing::Right => local.y,
+			Facing::Down => n - 1 - local.x,
+			Facing::Left => n - 1 - local.y,
+			Facing::Up => local.x
+		}
+	}
+
+	/// The inverse of [`edge_offset`](Self::edge_offset).
+	fn edge_point(&self, offset: i64, edge: Facing) -> Point {
+		let n = self.size;
+		match edge {
+			Facing::Right => Point::new(n - 1, offset),
+			Facing::Down => Point::new(n - 1 - offset, n - 1),
+			Facing::Left => Point::new(0, n - 1 - offset),
+			Facing::Up => Point::new(offset, 0)
+		}
+	}
+
+	/// Log a position both on the net and on its face of the cube.
+	pub(super) fn dbgpos(&self, pos: Point, facing: Facing) {
+		let face = self.face_at(pos).unwrap();
+		let origin = self.faces[face].origin;
+		debug!("Plane: y={}, x={}, facing={facing:?}", pos.y + 1, pos.x + 1);
+		debug!(
+			"Cube: face {face}, δy={}, δx={}, facing={facing:?}",
+			pos.y - origin.y + 1,
+			pos.x - origin.x + 1
+		);
+	}
+}
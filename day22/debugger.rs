@@ -0,0 +1,300 @@
+use super::{
+	cube::Cube,
+	render::render_around,
+	walker::{Event, Walker},
+	Facing
+};
+use crate::{BoundingBox, Point};
+use anyhow::{bail, Context as _};
+use std::{
+	fmt::{self, Display, Formatter},
+	io::{BufRead, Write}
+};
+
+/// A condition that stops the walk.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Breakpoint {
+	/// Stop when walking into a wall.
+	Wall,
+	/// Stop when wrapping around the edge of the map.
+	Wrap,
+	/// Stop when entering the region from outside.
+	Region(BoundingBox),
+	/// Stop when entering the face of the cube with this index from another face.
+	Face(usize)
+}
+
+impl Breakpoint {
+	fn hit(&self, event: Event, cube: Option<&Cube>) -> bool {
+		match (self, event) {
+			(Self::Wall, Event::HitWall(_)) => true,
+			(Self::Wrap, Event::Moved { wrapped, .. }) => wrapped,
+			(Self::Region(region), Event::Moved { from, to, .. }) => {
+				!region.contains(from) && region.contains(to)
+			},
+			(Self::Face(face), Event::Moved { from, to, .. }) => {
+				cube.is_some_and(|cube| {
+					cube.face_at(from) != Some(*face) && cube.face_at(to) == Some(*face)
+				})
+			},
+			_ => false
+		}
+	}
+}
+
+impl Display for Breakpoint {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Wall => f.write_str("wall"),
+			Self::Wrap => f.write_str("wrap"),
+			Self::Region(region) => write!(f, "region {} to {}", region.min, region.max),
+			Self::Face(face) => write!(f, "face {}", face + 1)
+		}
+	}
+}
+
+const HELP: &str = "\
+Positions are zero-based (x, y) coordinates on the net.
+
+next [N]                   follow the next N instructions (default 1)
+step [N]                   take N single steps or turns (default 1)
+continue                   follow the instructions until a breakpoint or the end
+break wall                 stop when walking into a wall
+break wrap                 stop when wrapping around the edge of the map
+break region X0 Y0 X1 Y1   stop when entering the region between both corners
+break face N               stop when entering face N of the cube, numbered from 1 like
+                           in `day22 trace --faces`
+breakpoints                list all breakpoints
+delete [N]                 delete breakpoint N, or all breakpoints
+show [RADIUS]              draw the tiles around the current position (default 5, at
+                           most 50)
+help                       print this help
+quit                       stop debugging
+";
+
+const RADIUS: usize = 5;
+
+/// The largest radius `show` draws, so that a typo doesn't draw millions of tiles.
+const MAX_RADIUS: usize = 50;
+
+/// Step through the instructions interactively.
+pub struct Debugger<'a> {
+	walker: Walker<'a>,
+	/// The map folded into a cube, if it is the net of one.
+	cube: Option<Cube>,
+	trail: Vec<(Point, Facing)>,
+	breakpoints: Vec<Breakpoint>
+}
+
+impl<'a> Debugger<'a> {
+	pub fn new(walker: Walker<'a>) -> Self {
+		let trail = vec![(walker.pos(), walker.facing())];
+		Self {
+			cube: Cube::fold(walker.map()).ok(),
+			walker,
+			trail,
+			breakpoints: Vec::new()
+		}
+	}
+
+	/// Read commands until the input ends or the user quits.
+	pub fn repl(
+		&mut self,
+		input: impl BufRead,
+		mut out: impl Write
+	) -> anyhow::Result<()> {
+		self.status(&mut out, "Ready")?;
+		write!(out, "> ")?;
+		out.flush()?;
+		for line in input.lines() {
+			match self.command(&line?, &mut out) {
+				Ok(true) => {},
+				Ok(false) => break,
+				Err(err) => writeln!(out, "Error: {err}")?
+			}
+			write!(out, "> ")?;
+			out.flush()?;
+		}
+		writeln!(out)?;
+		Ok(())
+	}
+
+	/// Run a single command. Returns false if the user wants to quit.
+	pub fn command(&mut self, line: &str, out: &mut impl Write) -> anyhow::Result<bool> {
+		let mut words = line.split_whitespace();
+		let Some(command) = words.next() else {
+			return Ok(true);
+		};
+		let args: Vec<&str> = words.collect();
+		let count = |default| -> anyhow::Result<usize> {
+			match args.first() {
+				Some(arg) => arg.parse().with_context(|| {
+					format!("Invalid number {arg:?}, expected 0 or more")
+				}),
+				None => Ok(default)
+			}
+		};
+
+		match command {
+			"n" | "next" => {
+				let mut reason = None;
+				for _ in 0 .. count(1)? {
+					reason = self.next_instruction();
+					if reason.is_some() {
+						break;
+					}
+				}
+				self.stop(out, reason)?;
+			},
+			"s" | "step" => {
+				let mut reason = None;
+				for _ in 0 .. count(1)? {
+					reason = self.tick();
+					if reason.is_some() {
+						break;
+					}
+				}
+				self.stop(out, reason)?;
+			},
+			"c" | "continue" => {
+				let reason = loop {
+					if let Some(reason) = self.tick() {
+						break reason;
+					}
+				};
+				self.stop(out, Some(reason))?;
+			},
+			"b" | "break" => {
+				let breakpoint = match args.as_slice() {
+					["wall"] => Breakpoint::Wall,
+					["wrap"] => Breakpoint::Wrap,
+					["region", coords @ ..] => {
+						let coords = coords
+							.iter()
+							.map(|arg| {
+								arg.parse().with_context(|| {
+									format!("Invalid coordinate {arg:?}")
+								})
+							})
+							.collect::<anyhow::Result<Vec<i64>>>()?;
+						let [x0, y0, x1, y1] = coords[..] else {
+							bail!("A region needs two corners, like `region 0 0 3 3`");
+						};
+						let corners = [Point::new(x0, y0), Point::new(x1, y1)];
+						Breakpoint::Region(BoundingBox::from_points(corners).unwrap())
+					},
+					["face", face] => {
+						if self.cube.is_none() {
+							bail!("The map is not the net of a cube, so it has no faces");
+						}
+						match face.parse::<usize>() {
+							Ok(face @ 1 ..= 6) => Breakpoint::Face(face - 1),
+							_ => bail!("Invalid face {face:?}, expected 1 to 6")
+						}
+					},
+					_ => bail!("Unknown breakpoint, try `help`")
+				};
+				writeln!(out, "Breakpoint {}: {breakpoint}", self.breakpoints.len())?;
+				self.breakpoints.push(breakpoint);
+			},
+			"breakpoints" => {
+				for (i, breakpoint) in self.breakpoints.iter().enumerate() {
+					writeln!(out, "Breakpoint {i}: {breakpoint}")?;
+				}
+			},
+			"d" | "delete" => match args.first() {
+				Some(_) => {
+					let i = count(0)?;
+					if i >= self.breakpoints.len() {
+						bail!("There is no breakpoint {i}");
+					}
+					self.breakpoints.remove(i);
+				},
+				None => self.breakpoints.clear()
+			},
+			"p" | "show" => {
+				let radius = count(RADIUS)?;
+				if radius > MAX_RADIUS {
+					bail!("The radius can be at most {MAX_RADIUS}");
+				}
+				self.status(out, "Stopped")?;
+				self.show(out, radius)?;
+			},
+			"h" | "help" => write!(out, "{HELP}")?,
+			"q" | "quit" => return Ok(false),
+			_ => bail!("Unknown command {command:?}, try `help`")
+		}
+		Ok(true)
+	}
+
+	/// Take a single step or turn, and return why we stopped if we hit a breakpoint or
+	/// the end of the instructions.
+	fn tick(&mut self) -> Option<String> {
+		let Some(event) = self.walker.tick() else {
+			return Some(format!("Done, the password is {}", self.walker.password()));
+		};
+		if let Event::Turned(_) | Event::Moved { .. } = event {
+			self.trail.push((self.walker.pos(), self.walker.facing()));
+		}
+		let hit = self
+			.breakpoints
+			.iter()
+			.position(|breakpoint| breakpoint.hit(event, self.cube.as_ref()))?;
+		Some(match event {
+			Event::HitWall(wall) => format!("Breakpoint {hit}: hit a wall at {wall}"),
+			Event::Moved { from, to, .. } => {
+				format!("Breakpoint {hit}: moved from {from} to {to}")
+			},
+			_ => format!("Breakpoint {hit}")
+		})
+	}
+
+	/// Finish the current instruction, or follow the next one.
+	fn next_instruction(&mut self) -> Option<String> {
+		if let Some(reason) = self.tick() {
+			return Some(reason);
+		}
+		while self.walker.in_instruction() {
+			if let Some(reason) = self.tick() {
+				return Some(reason);
+			}
+		}
+		None
+	}
+
+	fn stop(&self, out: &mut impl Write, reason: Option<String>) -> anyhow::Result<()> {
+		self.status(out, reason.as_deref().unwrap_or("Stopped"))?;
+		self.show(out, RADIUS)
+	}
+
+	fn status(&self, out: &mut impl Write, reason: &str) -> anyhow::Result<()> {
+		writeln!(
+			out,
+			"{reason}: at {} facing {}{}, {} instructions started{}",
+			self.walker.pos(),
+			self.walker.facing(),
+			if self.walker.mirrored() {
+				" (mirrored)"
+			} else {
+				""
+			},
+			self.walker.instructions_started(),
+			if self.walker.in_instruction() {
+				" (current one unfinished)"
+			} else {
+				""
+			}
+		)?;
+		Ok(())
+	}
+
+	fn show(&self, out: &mut impl Write, radius: usize) -> anyhow::Result<()> {
+		let map = self.walker.map();
+		write!(
+			out,
+			"{}",
+			render_around(map, &self.trail, self.walker.pos(), radius as i64)
+		)?;
+		Ok(())
+	}
+}
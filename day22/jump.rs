@@ -0,0 +1,156 @@
+use super::{topology::Topology, Facing, Instruction, Map, Tile};
+use crate::{Grid, Point};
+use log::debug;
+
+/// For every state, where we end up after taking some steps, so that moving doesn't
+/// need to walk tile by tile.
+///
+/// A state is an open tile together with a facing and whether we are on the mirrored
+/// side of the topology, numbered `8 * tile + 4 * mirrored + facing`.
+pub struct JumpTable {
+	tiles: Vec<Point>,
+	index: Grid<usize>,
+	/// For every level `i` and state, the state after walking `2^i` steps or until we
+	/// hit a wall.
+	jumps: Vec<Vec<usize>>,
+	/// For every state, the number of steps until we hit a wall, or `None` if we walk
+	/// in circles without ever hitting one.
+	to_wall: Vec<Option<usize>>,
+	/// For every state, the state right in front of the wall we are going to hit.
+	stop: Vec<usize>
+}
+
+impl JumpTable {
+	/// Build the table for the map on the given topology, with enough levels to take
+	/// `max_steps` steps in `O(log max_steps)`.
+	pub fn new(map: &Map, topology: &dyn Topology, max_steps: usize) -> Self {
+		let mut tiles = Vec::new();
+		let mut index = Grid::dense();
+		for (point, tile) in map.grid.iter() {
+			if *tile == Tile::Open {
+				index.insert(point, tiles.len());
+				tiles.push(point);
+			}
+		}
+
+		// a single step; states in front of a wall or the edge stay where they are
+		let next: Vec<usize> = (0 .. tiles.len() * 8)
+			.map(|state| {
+				let (pos, facing) = (tiles[state / 8], Facing::ALL[state % 4]);
+				let mirrored =
+					(state / 4 % 2) ^ topology.mirrors(map, pos, facing) as usize;
+				match topology.step(map, pos, facing) {
+					Some((next, next_facing)) => match index.get(next) {
+						Some(tile) => tile * 8 + mirrored * 4 + next_facing as usize,
+						None => state
+					},
+					None => state
+				}
+			})
+			.collect();
+
+		// distance to the next wall, following each state until we either reach a state
+		// we know about or end up in a circle
+		let mut to_wall: Vec<Option<Option<usize>>> = vec![None; next.len()];
+		let mut stop = vec![0; next.len()];
+		let mut on_path = vec![false; next.len()];
+		for start in 0 .. next.len() {
+			let mut path = Vec::new();
+			let mut state = start;
+			while to_wall[state].is_none() && !on_path[state] {
+				on_path[state] = true;
+				path.push(state);
+				if next[state] == state {
+					to_wall[state] = Some(Some(0));
+					stop[state] = state;
+					break;
+				}
+				state = next[state];
+			}
+			let circle = on_path[state] && to_wall[state].is_none();
+			for prev in path.into_iter().rev() {
+				on_path[prev] = false;
+				if to_wall[prev].is_some() {
+					continue;
+				}
+				let after = next[prev];
+				to_wall[prev] = Some(match circle {
+					true => None,
+					false => to_wall[after].unwrap().map(|steps| steps + 1)
+				});
+				stop[prev] = stop[after];
+			}
+		}
+		let to_wall: Vec<Option<usize>> =
+			to_wall.into_iter().map(Option::unwrap).collect();
+
+		// double the number of steps with every level
+		let mut jumps = vec![next];
+		while 1 << (jumps.len() - 1) < max_steps {
+			let last = jumps.last().unwrap();
+			jumps.push(last.iter().map(|state| last[*state]).collect());
+		}
+		debug!(
+			"Built a jump table for {} tiles with {} levels",
+			tiles.len(),
+			jumps.len()
+		);
+
+		Self {
+			tiles,
+			index,
+			jumps,
+			to_wall,
+			stop
+		}
+	}
+
+	/// Build the table with enough levels for the longest move of the instructions.
+	pub fn for_instructions(
+		map: &Map,
+		topology: &dyn Topology,
+		instructions: &[Instruction]
+	) -> Self {
+		let max_steps = instructions
+			.iter()
+			.map(|inst| match inst {
+				Instruction::Move(steps) => *steps,
+				_ => 0
+			})
+			.max()
+			.unwrap_or(0);
+		Self::new(map, topology, max_steps)
+	}
+
+	/// Take up to `steps` steps from an open tile, stopping in front of a wall.
+	pub fn jump(
+		&self,
+		pos: Point,
+		facing: Facing,
+		mirrored: bool,
+		steps: usize
+	) -> (Point, Facing, bool) {
+		let mut state = self.index[pos] * 8 + mirrored as usize * 4 + facing as usize;
+		match self.to_wall[state] {
+			Some(to_wall) if steps >= to_wall => state = self.stop[state],
+			_ => {
+				let top = self.jumps.len() - 1;
+				let mut steps = steps;
+				while steps >> top > 1 {
+					state = self.jumps[top][state];
+					steps -= 1 << top;
+				}
+				for (level, jumps) in self.jumps.iter().enumerate() {
+					if steps & (1 << level) != 0 {
+						state = jumps[state];
+					}
+				}
+			}
+		}
+		(
+			self.tiles[state / 8],
+			Facing::ALL[state % 4],
+			state / 4 % 2 == 1
+		)
+	}
+}
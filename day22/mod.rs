@@ -0,0 +1,255 @@
+pub mod cube;
+pub mod debugger;
+pub mod jump;
+pub mod render;
+pub mod topology;
+pub mod walker;
+
+use crate::{
+	read_validated, Answer, Diagnostic, Grid, InputSource, Label, Point, Solution
+};
+use anyhow::Context as _;
+use chumsky::{prelude::*, text::digits};
+use cube::Cube;
+use jump::JumpTable;
+use log::{debug, trace};
+use std::{
+	fmt::{self, Display, Formatter, Write as _},
+	ops::AddAssign
+};
+use topology::{Plain, Topology};
+use walker::{Event, Walker};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(usize)]
+pub enum Facing {
+	Right = 0,
+	Down = 1,
+	Left = 2,
+	Up = 3
+}
+
+impl Facing {
+	const ALL: [Self; 4] = [Self::Right, Self::Down, Self::Left, Self::Up];
+
+	pub fn opposite(self) -> Self {
+		let mut facing = self;
+		facing += 2;
+		facing
+	}
+
+	/// Turn a quarter clockwise or anticlockwise as seen by the walker. On the mirrored
+	/// side of a surface, the walker's clockwise is anticlockwise on the map.
+	fn turn(&mut self, clockwise: bool, mirrored: bool) {
+		*self += match clockwise != mirrored {
+			true => 1,
+			false => 3
+		};
+	}
+
+	fn dir(self) -> Point {
+		match self {
+			Self::Right => Point::RIGHT,
+			Self::Down => Point::DOWN,
+			Self::Left => Point::LEFT,
+			Self::Up => Point::UP
+		}
+	}
+}
+
+/// The markers used in the puzzle statement to draw a path.
+impl Display for Facing {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_char(match self {
+			Self::Right => '>',
+			Self::Down => 'v',
+			Self::Left => '<',
+			Self::Up => '^'
+		})
+	}
+}
+
+impl AddAssign<usize> for Facing {
+	fn add_assign(&mut self, rhs: usize) {
+		*self = match (*self as usize + rhs) % 4 {
+			0 => Self::Right,
+			1 => Self::Down,
+			2 => Self::Left,
+			3 => Self::Up,
+			_ => unreachable!()
+		}
+	}
+}
+
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
+enum Tile {
+	#[default]
+	Void,
+	Open,
+	Wall
+}
+
+impl Display for Tile {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_char(match self {
+			Self::Void => ' ',
+			Self::Open => '.',
+			Self::Wall => '#'
+		})
+	}
+}
+
+impl Tile {
+	fn parser() -> impl Parser<char, Self, Error = Simple<char>> + Clone {
+		choice((
+			just(" ").map(|_| Self::Void),
+			just(".").map(|_| Self::Open),
+			just("#").map(|_| Self::Wall)
+		))
+	}
+}
+
+pub struct Map {
+	grid: Grid<Tile>
+}
+
+impl Map {
+	fn parser() -> impl Parser<char, Self, Error = Simple<char>> {
+		Tile::parser()
+			.repeated()
+			.at_least(1)
+			.then_ignore(just("\n"))
+			.repeated()
+			.at_least(1)
+			.map(|rows| Self {
+				grid: Grid::from_rows(rows)
+			})
+	}
+
+	fn tile(&self, point: Point) -> Tile {
+		self.grid.get(point).copied().unwrap_or(Tile::Void)
+	}
+
+	/// The leftmost open tile of the top row, where the walk starts.
+	fn start(&self) -> anyhow::Result<Point> {
+		let bounds = self.grid.bounds().context("The map is empty")?;
+		(bounds.min.x ..= bounds.max.x)
+			.map(|x| Point::new(x, bounds.min.y))
+			.find(|pos| self.tile(*pos) == Tile::Open)
+			.context("The top row of the map has no open tile to start from")
+	}
+}
+
+#[derive(Debug)]
+pub enum Instruction {
+	Move(usize),
+	TurnClockwise,
+	TurnAnticlockwise
+}
+
+impl Instruction {
+	fn parser() -> impl Parser<char, Self, Error = Simple<char>> + Clone {
+		choice((
+			digits(10).map(|digits: String| Self::Move(digits.parse().unwrap())),
+			just("R").map(|_| Self::TurnClockwise),
+			just("L").map(|_| Self::TurnAnticlockwise)
+		))
+	}
+}
+
+fn parser() -> impl Parser<char, (Map, Vec<Instruction>), Error = Simple<char>> {
+	Map::parser()
+		.then_ignore(just("\n"))
+		.then(
+			Instruction::parser()
+				.recover_with(skip_then_retry_until(['\n']))
+				.repeated()
+				.at_least(1)
+		)
+		.then_ignore(just("\n"))
+		.then_ignore(end())
+}
+
+fn password(pos: Point, facing: Facing) -> usize {
+	(1000 * (pos.y + 1) + 4 * (pos.x + 1)) as usize + facing as usize
+}
+
+/// Follow the instructions on the given topology and return the final password. This
+/// uses a [`JumpTable`] so that every move takes `O(log n)` instead of `n` steps.
+pub fn walk(map: &Map, instructions: &[Instruction], topology: &dyn Topology) -> usize {
+	let table = JumpTable::for_instructions(map, topology, instructions);
+	let mut pos = map
+		.start()
+		.expect("parsing checks that the map has a start");
+	let mut facing = Facing::Right;
+	let mut mirrored = false;
+	for inst in instructions {
+		trace!("Plane: y={}, x={}, facing={facing:?}", pos.y + 1, pos.x + 1);
+		trace!(" == {inst:?} ==");
+		match inst {
+			Instruction::TurnClockwise => facing.turn(true, mirrored),
+			Instruction::TurnAnticlockwise => facing.turn(false, mirrored),
+			Instruction::Move(steps) => {
+				(pos, facing, mirrored) = table.jump(pos, facing, mirrored, *steps)
+			},
+		}
+	}
+
+	debug!(" == Result ==");
+	debug!("Plane: y={}, x={}, facing={facing:?}", pos.y + 1, pos.x + 1);
+	password(pos, facing)
+}
+
+/// Follow the instructions on the given topology one step at a time and return every
+/// position and facing along the way.
+pub fn trail(
+	map: &Map,
+	instructions: &[Instruction],
+	topology: &dyn Topology
+) -> Vec<(Point, Facing)> {
+	let mut walker = Walker::new(map, instructions, topology);
+	let mut trail = vec![(walker.pos(), walker.facing())];
+	while let Some(event) = walker.tick() {
+		if let Event::Turned(_) | Event::Moved { .. } = event {
+			trail.push((walker.pos(), walker.facing()));
+		}
+	}
+	trail
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+	type Input = (Map, Vec<Instruction>);
+	type Params = ();
+
+	const EXAMPLE: () = ();
+	const REAL: () = ();
+
+	fn parse(input: &InputSource) -> anyhow::Result<(Map, Vec<Instruction>)> {
+		read_validated(input, parser(), |text, (map, _)| match map.start() {
+			Ok(_) => Vec::new(),
+			Err(err) => {
+				let top = text.find('\n').unwrap_or(text.len());
+				vec![Diagnostic::custom(text, err.to_string(), vec![Label {
+					span: 0 .. top,
+					message: "There is nowhere to start on this row".to_owned()
+				}])]
+			}
+		})
+	}
+
+	fn part1(
+		(map, instructions): &(Map, Vec<Instruction>),
+		_: &()
+	) -> anyhow::Result<Answer> {
+		Ok(walk(map, instructions, &Plain).into())
+	}
+
+	fn part2(
+		(map, instructions): &(Map, Vec<Instruction>),
+		_: &()
+	) -> anyhow::Result<Answer> {
+		Ok(walk(map, instructions, &Cube::fold(map)?).into())
+	}
+}
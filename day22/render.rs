@@ -0,0 +1,89 @@
+use super::{cube::Cube, Facing, Map, Tile};
+use crate::{Grid, Point};
+use std::fmt::{self, Display, Formatter, Write as _};
+
+/// A tile of the map with the trail drawn on top of it.
+#[derive(Clone, Copy, Default)]
+enum Cell {
+	#[default]
+	Void,
+	Tile(Tile),
+	Trail(Facing)
+}
+
+impl Display for Cell {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Void => f.write_char(' '),
+			Self::Tile(tile) => tile.fmt(f),
+			Self::Trail(facing) => facing.fmt(f)
+		}
+	}
+}
+
+/// Draw the trail on top of the map. Like in the puzzle statement, every tile shows
+/// the last facing we had on it.
+fn overlay(map: &Map, trail: &[(Point, Facing)]) -> Grid<Cell> {
+	let mut grid = Grid::dense();
+	for (point, tile) in map.grid.iter() {
+		if *tile != Tile::Void {
+			grid.insert(point, Cell::Tile(*tile));
+		}
+	}
+	for (point, facing) in trail {
+		grid.insert(*point, Cell::Trail(*facing));
+	}
+	grid
+}
+
+/// Render the trail on the flat net, the way the puzzle statement does.
+pub fn render_net(map: &Map, trail: &[(Point, Facing)]) -> String {
+	overlay(map, trail)
+		.to_string()
+		.lines()
+		.map(|line| format!("{}\n", line.trim_end()))
+		.collect()
+}
+
+/// Render the trail separately for every face of the cube, in the order the faces
+/// appear in the net.
+pub fn render_faces(map: &Map, cube: &Cube, trail: &[(Point, Facing)]) -> String {
+	let grid = overlay(map, trail);
+	let mut out = String::new();
+	for (i, face) in cube.faces.iter().enumerate() {
+		if i > 0 {
+			out.push('\n');
+		}
+		let origin = face.origin;
+		writeln!(out, "Face {} at {origin}:", i + 1).unwrap();
+		for y in 0 .. cube.size {
+			for x in 0 .. cube.size {
+				let cell = grid.get(origin + Point::new(x, y)).copied();
+				write!(out, "{}", cell.unwrap_or_default()).unwrap();
+			}
+			out.push('\n');
+		}
+	}
+	out
+}
+
+/// Render the trail in a square around `center`, with the tiles at most `radius` tiles
+/// away in each direction.
+pub fn render_around(
+	map: &Map,
+	trail: &[(Point, Facing)],
+	center: Point,
+	radius: i64
+) -> String {
+	let grid = overlay(map, trail);
+	let mut out = String::new();
+	for y in center.y - radius ..= center.y + radius {
+		let mut line = String::new();
+		for x in center.x - radius ..= center.x + radius {
+			let cell = grid.get(Point::new(x, y)).copied();
+			write!(line, "{}", cell.unwrap_or_default()).unwrap();
+		}
+		writeln!(out, "{}", line.trim_end()).unwrap();
+	}
+	out
+}
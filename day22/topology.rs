@@ -0,0 +1,138 @@
+use super::{cube::Cube, Facing, Map, Tile};
+use crate::Point;
+use clap::ValueEnum;
+use log::debug;
+
+/// The surface the map is drawn on, which decides what happens when walking off the
+/// edge of the map.
+pub trait Topology {
+	/// Take a single step from `pos` in the direction of `facing`, ignoring walls.
+	/// Returns the new position and facing, or `None` if there is nowhere to go.
+	fn step(&self, map: &Map, pos: Point, facing: Facing) -> Option<(Point, Facing)>;
+
+	/// Whether the step from `pos` in the direction of `facing` crosses an edge that is
+	/// glued together mirrored, which swaps left and right until crossing back.
+	fn mirrors(&self, _map: &Map, _pos: Point, _facing: Facing) -> bool {
+		false
+	}
+}
+
+/// The surfaces that come with the solution.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum TopologyKind {
+	/// Wrap around to the other end of the row or column, like in part 1.
+	Plain,
+	/// Fold the map into a cube, like in part 2.
+	Cube,
+	/// Glue opposite edges of the bounding box together. Void tiles are holes.
+	Torus,
+	/// Like a torus, but the top and bottom edge are glued together mirrored, which
+	/// also mirrors turns until crossing back. Void tiles are holes.
+	Klein,
+	/// Stop at the edge of the map.
+	Clamped
+}
+
+impl TopologyKind {
+	pub fn build(self, map: &Map) -> anyhow::Result<Box<dyn Topology>> {
+		Ok(match self {
+			Self::Plain => Box::new(Plain),
+			Self::Cube => Box::new(Cube::fold(map)?),
+			Self::Torus => Box::new(Torus),
+			Self::Klein => Box::new(Klein),
+			Self::Clamped => Box::new(Clamped)
+		})
+	}
+}
+
+/// Wrap around to the other end of the row or column, skipping over the void.
+pub struct Plain;
+
+impl Topology for Plain {
+	fn step(&self, map: &Map, pos: Point, facing: Facing) -> Option<(Point, Facing)> {
+		let bounds = map.grid.bounds()?;
+		let mut next = pos;
+		loop {
+			next += facing.dir();
+			next.x =
+				(next.x - bounds.min.x).rem_euclid(bounds.width() as i64) + bounds.min.x;
+			next.y =
+				(next.y - bounds.min.y).rem_euclid(bounds.height() as i64) + bounds.min.y;
+			if map.tile(next) != Tile::Void {
+				return Some((next, facing));
+			}
+		}
+	}
+}
+
+impl Topology for Cube {
+	fn step(&self, _: &Map, pos: Point, facing: Facing) -> Option<(Point, Facing)> {
+		let (next, next_facing) = self.step(pos, facing);
+		if next != pos + facing.dir() {
+			debug!("!! Wrapped from:");
+			self.dbgpos(pos, facing);
+			debug!("-- to:");
+			self.dbgpos(next, next_facing);
+		}
+		Some((next, next_facing))
+	}
+}
+
+/// Glue the left and right as well as the top and bottom edge of the bounding box
+/// together.
+pub struct Torus;
+
+impl Topology for Torus {
+	fn step(&self, map: &Map, pos: Point, facing: Facing) -> Option<(Point, Facing)> {
+		let bounds = map.grid.bounds()?;
+		let mut next = pos + facing.dir();
+		next.x = (next.x - bounds.min.x).rem_euclid(bounds.width() as i64) + bounds.min.x;
+		next.y =
+			(next.y - bounds.min.y).rem_euclid(bounds.height() as i64) + bounds.min.y;
+		(map.tile(next) != Tile::Void).then_some((next, facing))
+	}
+}
+
+/// Glue the left and right edge of the bounding box together, and the top and bottom
+/// edge with a twist, so that walking off the top puts you at the mirrored column at
+/// the bottom. After the twist, left and right are swapped: turning clockwise turns
+/// anticlockwise on the map, until the walker crosses the twist again.
+pub struct Klein;
+
+impl Klein {
+	fn crosses_twist(map: &Map, pos: Point, facing: Facing) -> bool {
+		let Some(bounds) = map.grid.bounds() else {
+			return false;
+		};
+		let next = pos + facing.dir();
+		!(bounds.min.y ..= bounds.max.y).contains(&next.y)
+	}
+}
+
+impl Topology for Klein {
+	fn step(&self, map: &Map, pos: Point, facing: Facing) -> Option<(Point, Facing)> {
+		let bounds = map.grid.bounds()?;
+		let mut next = pos + facing.dir();
+		next.x = (next.x - bounds.min.x).rem_euclid(bounds.width() as i64) + bounds.min.x;
+		if Self::crosses_twist(map, pos, facing) {
+			next.x = bounds.min.x + bounds.max.x - next.x;
+			next.y =
+				(next.y - bounds.min.y).rem_euclid(bounds.height() as i64) + bounds.min.y;
+		}
+		(map.tile(next) != Tile::Void).then_some((next, facing))
+	}
+
+	fn mirrors(&self, map: &Map, pos: Point, facing: Facing) -> bool {
+		Self::crosses_twist(map, pos, facing)
+	}
+}
+
+/// Never wrap around, walking off the map is like walking into a wall.
+pub struct Clamped;
+
+impl Topology for Clamped {
+	fn step(&self, map: &Map, pos: Point, facing: Facing) -> Option<(Point, Facing)> {
+		let next = pos + facing.dir();
+		(map.tile(next) != Tile::Void).then_some((next, facing))
+	}
+}
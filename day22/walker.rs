@@ -0,0 +1,142 @@
+use super::{password, topology::Topology, Facing, Instruction, Map, Tile};
+use crate::Point;
+use log::trace;
+
+/// Something that happened while following the instructions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Event {
+	/// We turned and are now facing this way.
+	Turned(Facing),
+	/// We took a single step. The step wrapped around if we didn't end up right next to
+	/// where we started.
+	Moved {
+		from: Point,
+		to: Point,
+		wrapped: bool
+	},
+	/// We couldn't move further because the next tile is a wall.
+	HitWall(Point),
+	/// We couldn't move further because there is nowhere to go on the topology.
+	HitEdge
+}
+
+/// Follows the instructions one step at a time.
+pub struct Walker<'a> {
+	map: &'a Map,
+	instructions: &'a [Instruction],
+	topology: &'a dyn Topology,
+	pos: Point,
+	facing: Facing,
+	/// Whether we are on the mirrored side of the topology, where turns are swapped.
+	mirrored: bool,
+	/// The index of the next instruction to start.
+	next: usize,
+	/// The steps left to take for the current move instruction.
+	steps_left: usize
+}
+
+impl<'a> Walker<'a> {
+	/// Start at the leftmost open tile of the top row, facing right.
+	pub fn new(
+		map: &'a Map,
+		instructions: &'a [Instruction],
+		topology: &'a dyn Topology
+	) -> Self {
+		Self {
+			map,
+			instructions,
+			topology,
+			pos: map
+				.start()
+				.expect("parsing checks that the map has a start"),
+			facing: Facing::Right,
+			mirrored: false,
+			next: 0,
+			steps_left: 0
+		}
+	}
+
+	pub fn map(&self) -> &'a Map {
+		self.map
+	}
+
+	pub fn pos(&self) -> Point {
+		self.pos
+	}
+
+	pub fn facing(&self) -> Facing {
+		self.facing
+	}
+
+	pub fn mirrored(&self) -> bool {
+		self.mirrored
+	}
+
+	/// The number of instructions that have been started.
+	pub fn instructions_started(&self) -> usize {
+		self.next
+	}
+
+	/// Returns true while an instruction is only partially executed.
+	pub fn in_instruction(&self) -> bool {
+		self.steps_left > 0
+	}
+
+	pub fn is_done(&self) -> bool {
+		self.steps_left == 0 && self.next == self.instructions.len()
+	}
+
+	/// The password for the current position and facing.
+	pub fn password(&self) -> usize {
+		password(self.pos, self.facing)
+	}
+
+	/// Take a single step or turn. Returns `None` once all instructions are done.
+	pub fn tick(&mut self) -> Option<Event> {
+		while self.steps_left == 0 {
+			let inst = self.instructions.get(self.next)?;
+			self.next += 1;
+			trace!(
+				"Plane: y={}, x={}, facing={:?}",
+				self.pos.y + 1,
+				self.pos.x + 1,
+				self.facing
+			);
+			trace!(" == {inst:?} ==");
+			match inst {
+				Instruction::TurnClockwise => {
+					self.facing.turn(true, self.mirrored);
+					return Some(Event::Turned(self.facing));
+				},
+				Instruction::TurnAnticlockwise => {
+					self.facing.turn(false, self.mirrored);
+					return Some(Event::Turned(self.facing));
+				},
+				Instruction::Move(steps) => self.steps_left = *steps
+			}
+		}
+
+		let Some((next, next_facing)) =
+			self.topology.step(self.map, self.pos, self.facing)
+		else {
+			trace!("    (hit the edge)");
+			self.steps_left = 0;
+			return Some(Event::HitEdge);
+		};
+		if self.map.tile(next) == Tile::Wall {
+			trace!("    (hit wall at {next})");
+			self.steps_left = 0;
+			return Some(Event::HitWall(next));
+		}
+		let from = self.pos;
+		let wrapped = next != from + self.facing.dir();
+		self.mirrored ^= self.topology.mirrors(self.map, self.pos, self.facing);
+		(self.pos, self.facing) = (next, next_facing);
+		self.steps_left -= 1;
+		Some(Event::Moved {
+			from,
+			to: next,
+			wrapped
+		})
+	}
+}
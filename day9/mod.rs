@@ -0,0 +1,157 @@
+use crate::{lines, read, Answer, Grid, InputSource, Point, Solution};
+use chumsky::{prelude::*, text::digits};
+use log::{debug, trace};
+use std::fmt::{self, Display, Formatter, Write as _};
+
+#[derive(Clone, Copy, Debug)]
+enum Direction {
+	Right,
+	Left,
+	Up,
+	Down
+}
+
+#[derive(Debug)]
+pub struct Move {
+	direction: Direction,
+	length: i64
+}
+
+impl Move {
+	fn parser() -> impl Parser<char, Self, Error = Simple<char>> {
+		let direction = choice((
+			just("R").to(Direction::Right),
+			just("L").to(Direction::Left),
+			just("U").to(Direction::Up),
+			just("D").to(Direction::Down)
+		));
+		let length = digits(10).try_map(|digits: String, span| {
+			digits
+				.parse()
+				.map_err(|_| Simple::custom(span, format!("Invalid length: {digits}")))
+		});
+		direction
+			.then_ignore(just(" "))
+			.then(length)
+			.map(|(direction, length)| Self { direction, length })
+	}
+}
+
+fn parser() -> impl Parser<char, Vec<Move>, Error = Simple<char>> {
+	lines(Move::parser()).then_ignore(end())
+}
+
+/// A cell of the rendered rope.
+#[derive(Clone, Copy)]
+struct Cell(char);
+
+impl Default for Cell {
+	fn default() -> Self {
+		Self('.')
+	}
+}
+
+impl Display for Cell {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_char(self.0)
+	}
+}
+
+fn render(head: Point, tails: &[Point], visited: &Grid<()>) -> Grid<Cell> {
+	let mut grid = Grid::sparse();
+	grid.insert(Point::ORIGIN, Cell('s'));
+	for (pos, _) in visited.iter() {
+		grid.insert(pos, Cell('#'));
+	}
+	for (i, pos) in tails.iter().enumerate() {
+		let ch = match i == tails.len() - 1 {
+			true => 'T',
+			false => char::from_digit(i as u32 + 1, 10).unwrap()
+		};
+		grid.insert(*pos, Cell(ch));
+	}
+	grid.insert(head, Cell('H'));
+	grid
+}
+
+fn run<const TAILS: usize>(moves: &[Move]) -> usize {
+	let mut head = Point::ORIGIN;
+	let mut tails = [Point::ORIGIN; TAILS];
+
+	let mut visited = Grid::sparse();
+	visited.insert(*tails.last().unwrap(), ());
+
+	for m in moves {
+		debug!("== {m:?} ==");
+
+		match m.direction {
+			Direction::Right => head.x += m.length,
+			Direction::Left => head.x -= m.length,
+			Direction::Up => head.y -= m.length,
+			Direction::Down => head.y += m.length
+		}
+		trace!("\n{}", render(head, &tails, &visited));
+
+		let mut done = false;
+		while !done {
+			done = true;
+			for i in 0 .. TAILS {
+				let prev = match i {
+					0 => &head,
+					_ => &tails[i - 1]
+				};
+
+				let xdist = prev.x - tails[i].x;
+				let xdiff = xdist.abs();
+
+				let ydist = prev.y - tails[i].y;
+				let ydiff = ydist.abs();
+
+				if xdiff >= 2 || ydiff >= 2 {
+					if xdiff >= 1 {
+						tails[i].x += xdist / xdiff
+					}
+					if ydiff >= 1 {
+						tails[i].y += ydist / ydiff
+					}
+				}
+
+				if i == TAILS - 1 {
+					visited.insert(tails[i], ());
+				}
+
+				if xdiff > 2 || ydiff > 2 {
+					done = false;
+				}
+			}
+		}
+
+		trace!("\n{}", render(head, &tails, &visited));
+	}
+
+	debug!("== RESULT ==\n{}", render(head, &tails, &visited));
+
+	visited.len()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+	type Input = Vec<Move>;
+	type Params = ();
+
+	const EXAMPLE: () = ();
+	const REAL: () = ();
+
+	fn parse(input: &InputSource) -> anyhow::Result<Vec<Move>> {
+		read(input, parser())
+	}
+
+	fn part1(moves: &Vec<Move>, _: &()) -> anyhow::Result<Answer> {
+		Ok(run::<1>(moves).into())
+	}
+
+	fn part2(moves: &Vec<Move>, _: &()) -> anyhow::Result<Answer> {
+		Ok(run::<9>(moves).into())
+	}
+}
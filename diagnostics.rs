@@ -0,0 +1,224 @@
+use ariadne::{Report, ReportKind, Source};
+use chumsky::{error::SimpleReason, prelude::*};
+use serde::Serialize;
+use std::{
+	error::Error,
+	fmt::{self, Display, Formatter, Write as _},
+	io::{self, Write},
+	ops::Range
+};
+
+/// A span of the input with a message attached to it.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Label {
+	pub span: Range<usize>,
+	pub message: String
+}
+
+/// A single error that was encountered while parsing the input.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Diagnostic {
+	pub message: String,
+	/// The span of the input where the error occurred, in chars.
+	pub span: Range<usize>,
+	/// The line of the start of the span, starting at 1.
+	pub line: usize,
+	/// The column of the start of the span, starting at 1.
+	pub column: usize,
+	pub labels: Vec<Label>,
+	/// The tokens that were expected. `None` stands for the end of the input.
+	pub expected: Vec<Option<String>>,
+	/// The token that was found. `None` stands for the end of the input.
+	pub found: Option<String>,
+	pub note: Option<String>
+}
+
+/// Return the line and column of the char at `offset`, both starting at 1.
+fn position(text: &str, offset: usize) -> (usize, usize) {
+	let mut line = 1;
+	let mut column = 1;
+	for ch in text.chars().take(offset) {
+		if ch == '\n' {
+			line += 1;
+			column = 1;
+		} else {
+			column += 1;
+		}
+	}
+	(line, column)
+}
+
+impl Diagnostic {
+	/// An error that was found after parsing, like a reference to something that doesn't
+	/// exist. The diagnostic starts where the first label does.
+	pub fn custom(text: &str, message: impl Into<String>, labels: Vec<Label>) -> Self {
+		let span = labels
+			.first()
+			.map(|label| label.span.clone())
+			.unwrap_or(0 .. 0);
+		let (line, column) = position(text, span.start);
+		Self {
+			message: message.into(),
+			span,
+			line,
+			column,
+			labels,
+			expected: Vec::new(),
+			found: None,
+			note: None
+		}
+	}
+
+	fn new(text: &str, e: Simple<char>) -> Self {
+		let (line, column) = position(text, e.span().start);
+		let expected: Vec<_> =
+			e.expected().map(|ex| ex.map(|ex| ex.to_string())).collect();
+		let found = e.found().map(|found| found.to_string());
+
+		let mut labels = Vec::new();
+		let mut note = None;
+		let message = match (e.reason(), e.found()) {
+			(SimpleReason::Unexpected, Some(found)) => {
+				labels.push(Label {
+					span: e.span(),
+					message: format!("Unexpected token {found}")
+				});
+				if !expected.is_empty() {
+					note = Some(format!(
+						"Expected {}",
+						e.expected()
+							.map(|ex| match ex {
+								Some(ex) => format!("{ex:?}"),
+								None => "end of file".to_owned()
+							})
+							.collect::<Vec<_>>()
+							.join(", ")
+					));
+				}
+				"Unexpected token".to_owned()
+			},
+
+			(SimpleReason::Unexpected, None) => "Unexpected end of file".to_owned(),
+
+			(SimpleReason::Unclosed { span, delimiter }, found) => {
+				labels.push(Label {
+					span: span.clone(),
+					message: format!("Unclosed delimiter {delimiter}")
+				});
+				if let Some(found) = found {
+					labels.push(Label {
+						span: e.span(),
+						message: format!("Must be closed before this {found}")
+					});
+				}
+				"Unclosed delimiter".to_owned()
+			},
+
+			(SimpleReason::Custom(msg), _) => {
+				labels.push(Label {
+					span: e.span(),
+					message: msg.clone()
+				});
+				msg.clone()
+			}
+		};
+
+		Self {
+			message,
+			span: e.span(),
+			line,
+			column,
+			labels,
+			expected,
+			found,
+			note
+		}
+	}
+}
+
+/// The input could not be parsed.
+#[derive(Clone, Debug, Serialize)]
+pub struct ParseError {
+	/// The name of the input, usually its path.
+	pub name: String,
+	/// The text of the input.
+	#[serde(skip)]
+	pub text: String,
+	pub diagnostics: Vec<Diagnostic>
+}
+
+impl Display for ParseError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "Failed to parse {}", self.name)
+	}
+}
+
+impl Error for ParseError {}
+
+impl ParseError {
+	pub(crate) fn new(name: String, text: String, errors: Vec<Simple<char>>) -> Self {
+		let diagnostics = errors
+			.into_iter()
+			.map(|e| Diagnostic::new(&text, e))
+			.collect();
+		Self {
+			name,
+			text,
+			diagnostics
+		}
+	}
+
+	/// Write the diagnostics as pretty terminal output using ariadne.
+	pub fn write_fancy<W: Write>(&self, mut w: W) -> io::Result<()> {
+		let name = self.name.as_str();
+		for d in &self.diagnostics {
+			let mut report = Report::build(ReportKind::Error, name, d.span.start);
+			report.set_message(&d.message);
+			for label in &d.labels {
+				report.add_label(
+					ariadne::Label::new((name, label.span.clone()))
+						.with_message(&label.message)
+				);
+			}
+			if let Some(note) = &d.note {
+				report.set_note(note);
+			}
+			report
+				.finish()
+				.write((name, Source::from(&self.text)), &mut w)?;
+		}
+		Ok(())
+	}
+
+	/// Render the diagnostics as plain text, one line per diagnostic and label, in the
+	/// `path:line:column: message` format understood by most editors.
+	pub fn to_plain(&self) -> String {
+		let mut buf = String::new();
+		for d in &self.diagnostics {
+			writeln!(
+				buf,
+				"{}:{}:{}: error: {}",
+				self.name, d.line, d.column, d.message
+			)
+			.unwrap();
+			for label in &d.labels {
+				let (line, column) = position(&self.text, label.span.start);
+				writeln!(
+					buf,
+					"{}:{line}:{column}: note: {}",
+					self.name, label.message
+				)
+				.unwrap();
+			}
+			if let Some(note) = &d.note {
+				writeln!(buf, "  = note: {note}").unwrap();
+			}
+		}
+		buf
+	}
+
+	/// Render the diagnostics as JSON.
+	pub fn to_json(&self) -> String {
+		serde_json::to_string(self).unwrap()
+	}
+}
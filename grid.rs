@@ -0,0 +1,419 @@
+use std::{
+	collections::HashMap,
+	fmt::{self, Display, Formatter},
+	ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign}
+};
+
+/// A position on a [`Grid`]. The x axis grows to the right, and the y axis grows
+/// downwards, the same way the puzzle inputs are written.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point {
+	pub x: i64,
+	pub y: i64
+}
+
+impl Point {
+	pub const ORIGIN: Self = Self::new(0, 0);
+	pub const UP: Self = Self::new(0, -1);
+	pub const DOWN: Self = Self::new(0, 1);
+	pub const LEFT: Self = Self::new(-1, 0);
+	pub const RIGHT: Self = Self::new(1, 0);
+
+	pub const fn new(x: i64, y: i64) -> Self {
+		Self { x, y }
+	}
+
+	pub fn manhattan_dist(self, other: Self) -> u64 {
+		self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+	}
+
+	/// The four points that share an edge with this point.
+	pub fn neighbours4(self) -> [Self; 4] {
+		[Self::UP, Self::RIGHT, Self::DOWN, Self::LEFT].map(|dir| self + dir)
+	}
+
+	/// The eight points that share an edge or a corner with this point.
+	pub fn neighbours8(self) -> [Self; 8] {
+		[
+			Self::UP,
+			Self::UP + Self::RIGHT,
+			Self::RIGHT,
+			Self::DOWN + Self::RIGHT,
+			Self::DOWN,
+			Self::DOWN + Self::LEFT,
+			Self::LEFT,
+			Self::UP + Self::LEFT
+		]
+		.map(|dir| self + dir)
+	}
+}
+
+impl Display for Point {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "({}, {})", self.x, self.y)
+	}
+}
+
+impl Add for Point {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self {
+		Self::new(self.x + rhs.x, self.y + rhs.y)
+	}
+}
+
+impl AddAssign for Point {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs;
+	}
+}
+
+impl Sub for Point {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self {
+		Self::new(self.x - rhs.x, self.y - rhs.y)
+	}
+}
+
+impl SubAssign for Point {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs;
+	}
+}
+
+impl Mul<i64> for Point {
+	type Output = Self;
+
+	fn mul(self, rhs: i64) -> Self {
+		Self::new(self.x * rhs, self.y * rhs)
+	}
+}
+
+impl Neg for Point {
+	type Output = Self;
+
+	fn neg(self) -> Self {
+		Self::new(-self.x, -self.y)
+	}
+}
+
+/// A rectangle on a [`Grid`]. Both corners are inclusive.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BoundingBox {
+	pub min: Point,
+	pub max: Point
+}
+
+impl BoundingBox {
+	/// A bounding box containing only a single point.
+	pub fn new(point: Point) -> Self {
+		Self {
+			min: point,
+			max: point
+		}
+	}
+
+	/// The smallest bounding box containing all points, or `None` if there are none.
+	pub fn from_points<I>(points: I) -> Option<Self>
+	where
+		I: IntoIterator<Item = Point>
+	{
+		let mut points = points.into_iter();
+		let mut bounds = Self::new(points.next()?);
+		for point in points {
+			bounds.include(point);
+		}
+		Some(bounds)
+	}
+
+	/// Grow the bounding box so that it contains the point.
+	pub fn include(&mut self, point: Point) {
+		self.min.x = self.min.x.min(point.x);
+		self.min.y = self.min.y.min(point.y);
+		self.max.x = self.max.x.max(point.x);
+		self.max.y = self.max.y.max(point.y);
+	}
+
+	pub fn contains(&self, point: Point) -> bool {
+		(self.min.x ..= self.max.x).contains(&point.x)
+			&& (self.min.y ..= self.max.y).contains(&point.y)
+	}
+
+	pub fn width(&self) -> usize {
+		(self.max.x - self.min.x + 1) as usize
+	}
+
+	pub fn height(&self) -> usize {
+		(self.max.y - self.min.y + 1) as usize
+	}
+
+	/// All points inside the bounding box, row by row.
+	pub fn points(self) -> impl Iterator<Item = Point> {
+		(self.min.y ..= self.max.y)
+			.flat_map(move |y| (self.min.x ..= self.max.x).map(move |x| Point::new(x, y)))
+	}
+
+	fn index(&self, point: Point) -> usize {
+		(point.y - self.min.y) as usize * self.width() + (point.x - self.min.x) as usize
+	}
+}
+
+#[derive(Clone, Debug)]
+enum Storage<T> {
+	/// All cells inside `area` are allocated, whether they are set or not.
+	Dense {
+		area: Option<BoundingBox>,
+		cells: Vec<Option<T>>
+	},
+	Sparse(HashMap<Point, T>)
+}
+
+/// The index of the point in the cells of a dense grid, if it is allocated.
+fn slot(area: &Option<BoundingBox>, point: Point) -> Option<usize> {
+	area.filter(|area| area.contains(point))
+		.map(|area| area.index(point))
+}
+
+/// A two-dimensional grid of cells. Every cell of the grid is either set to some value,
+/// or missing.
+///
+/// The dense backend allocates every cell inside the bounding box, which is fast for
+/// grids where most cells are set, like most puzzle inputs. It grows automatically when
+/// inserting outside of the allocated area. The sparse backend only stores the cells
+/// that were set, which is better when they are few and far between.
+#[derive(Clone, Debug)]
+pub struct Grid<T> {
+	storage: Storage<T>,
+	bounds: Option<BoundingBox>,
+	len: usize
+}
+
+impl<T> Default for Grid<T> {
+	fn default() -> Self {
+		Self::dense()
+	}
+}
+
+impl<T> Grid<T> {
+	/// An empty grid using the dense backend.
+	pub fn dense() -> Self {
+		Self {
+			storage: Storage::Dense {
+				area: None,
+				cells: Vec::new()
+			},
+			bounds: None,
+			len: 0
+		}
+	}
+
+	/// An empty grid using the sparse backend.
+	pub fn sparse() -> Self {
+		Self {
+			storage: Storage::Sparse(HashMap::new()),
+			bounds: None,
+			len: 0
+		}
+	}
+
+	/// A dense grid with the first row at y = 0 and the first column at x = 0. The rows
+	/// may have different lengths.
+	pub fn from_rows<R, C>(rows: R) -> Self
+	where
+		R: IntoIterator<Item = C>,
+		C: IntoIterator<Item = T>
+	{
+		let mut grid = Self::dense();
+		for (y, row) in rows.into_iter().enumerate() {
+			for (x, value) in row.into_iter().enumerate() {
+				grid.insert(Point::new(x as i64, y as i64), value);
+			}
+		}
+		grid
+	}
+
+	/// The number of cells that are set.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// The bounding box of all cells that have been set, or `None` if no cell has ever
+	/// been set. Removing cells does not shrink the bounding box.
+	pub fn bounds(&self) -> Option<BoundingBox> {
+		self.bounds
+	}
+
+	pub fn get(&self, point: Point) -> Option<&T> {
+		match &self.storage {
+			Storage::Dense { area, cells } => cells[slot(area, point)?].as_ref(),
+			Storage::Sparse(cells) => cells.get(&point)
+		}
+	}
+
+	pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+		match &mut self.storage {
+			Storage::Dense { area, cells } => cells[slot(area, point)?].as_mut(),
+			Storage::Sparse(cells) => cells.get_mut(&point)
+		}
+	}
+
+	pub fn contains(&self, point: Point) -> bool {
+		self.get(point).is_some()
+	}
+
+	/// Set a cell, returning its previous value.
+	pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+		match &mut self.bounds {
+			Some(bounds) => bounds.include(point),
+			None => self.bounds = Some(BoundingBox::new(point))
+		}
+		let old = match &mut self.storage {
+			Storage::Dense { area, cells } => {
+				let idx = match slot(area, point) {
+					Some(idx) => idx,
+					None => Self::grow(area, cells, point)
+				};
+				cells[idx].replace(value)
+			},
+			Storage::Sparse(cells) => cells.insert(point, value)
+		};
+		if old.is_none() {
+			self.len += 1;
+		}
+		old
+	}
+
+	/// Reallocate a dense grid so that it includes the point, and return the index of
+	/// the point. To avoid reallocating all the time, the area grows by at least its
+	/// current size in that direction.
+	fn grow(
+		area: &mut Option<BoundingBox>,
+		cells: &mut Vec<Option<T>>,
+		point: Point
+	) -> usize {
+		let Some(area) = area.as_mut() else {
+			*area = Some(BoundingBox::new(point));
+			cells.push(None);
+			return 0;
+		};
+		let mut new_area = *area;
+		let width = area.width() as i64;
+		let height = area.height() as i64;
+		if point.x < area.min.x {
+			new_area.min.x = point.x.min(area.min.x - width);
+		} else if point.x > area.max.x {
+			new_area.max.x = point.x.max(area.max.x + width);
+		}
+		if point.y < area.min.y {
+			new_area.min.y = point.y.min(area.min.y - height);
+		} else if point.y > area.max.y {
+			new_area.max.y = point.y.max(area.max.y + height);
+		}
+
+		let mut new_cells: Vec<Option<T>> = Vec::new();
+		new_cells.resize_with(new_area.width() * new_area.height(), || None);
+		for (point, cell) in area.points().zip(cells.drain(..)) {
+			new_cells[new_area.index(point)] = cell;
+		}
+		*area = new_area;
+		*cells = new_cells;
+		new_area.index(point)
+	}
+
+	/// Unset a cell, returning its previous value.
+	pub fn remove(&mut self, point: Point) -> Option<T> {
+		let old = match &mut self.storage {
+			Storage::Dense { area, cells } => cells[slot(area, point)?].take(),
+			Storage::Sparse(cells) => cells.remove(&point)
+		};
+		if old.is_some() {
+			self.len -= 1;
+		}
+		old
+	}
+
+	/// All cells that are set, in no particular order.
+	pub fn iter(&self) -> Box<dyn Iterator<Item = (Point, &T)> + '_> {
+		match &self.storage {
+			Storage::Dense { area, cells } => Box::new(
+				area.iter()
+					.flat_map(|area| area.points())
+					.zip(cells)
+					.filter_map(|(point, cell)| Some((point, cell.as_ref()?)))
+			),
+			Storage::Sparse(cells) => {
+				Box::new(cells.iter().map(|(point, cell)| (*point, cell)))
+			},
+		}
+	}
+
+	/// The values of all cells that are set, in no particular order.
+	pub fn values(&self) -> impl Iterator<Item = &T> {
+		self.iter().map(|(_, value)| value)
+	}
+
+	/// The neighbouring cells that share an edge with the point and are set.
+	pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+		point
+			.neighbours4()
+			.into_iter()
+			.filter_map(|point| Some((point, self.get(point)?)))
+	}
+
+	/// The neighbouring cells that share an edge or a corner with the point and are set.
+	pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+		point
+			.neighbours8()
+			.into_iter()
+			.filter_map(|point| Some((point, self.get(point)?)))
+	}
+}
+
+impl<T> Index<Point> for Grid<T> {
+	type Output = T;
+
+	fn index(&self, point: Point) -> &T {
+		self.get(point)
+			.unwrap_or_else(|| panic!("No cell at {point}"))
+	}
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+	fn index_mut(&mut self, point: Point) -> &mut T {
+		self.get_mut(point)
+			.unwrap_or_else(|| panic!("No cell at {point}"))
+	}
+}
+
+impl<T> FromIterator<(Point, T)> for Grid<T> {
+	fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+		let mut grid = Self::sparse();
+		for (point, value) in iter {
+			grid.insert(point, value);
+		}
+		grid
+	}
+}
+
+/// Render all cells inside the bounding box, one line per row. Cells that are not set
+/// are rendered as the default value.
+impl<T: Default + Display> Display for Grid<T> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let Some(bounds) = self.bounds else {
+			return Ok(());
+		};
+		let missing = T::default();
+		for y in bounds.min.y ..= bounds.max.y {
+			for x in bounds.min.x ..= bounds.max.x {
+				write!(f, "{}", self.get(Point::new(x, y)).unwrap_or(&missing))?;
+			}
+			writeln!(f)?;
+		}
+		Ok(())
+	}
+}
@@ -1,71 +1,371 @@
-use anyhow::anyhow;
-use ariadne::{Label, Report, ReportKind, Source};
-use chumsky::{error::SimpleReason, prelude::*};
-use std::{fs, path::Path};
-
-fn report_err(buf: &str, path_str: &str, err: Vec<Simple<char>>) {
-	for e in err {
-		let mut report = Report::build(ReportKind::Error, path_str, e.span().start);
-		match (e.reason(), e.found()) {
-			(SimpleReason::Unexpected, Some(found)) => {
-				report.set_message("Unexpected token");
-				report.add_label(
-					Label::new((path_str, e.span()))
-						.with_message(format!("Unexpected token {found}"))
-				);
-				if e.expected().len() > 0 {
-					report.set_note(format!(
-						"Expected {}",
-						e.expected()
-							.map(|ex| match ex {
-								Some(ex) => format!("{ex:?}"),
-								None => "end of file".to_owned()
-							})
-							.collect::<Vec<_>>()
-							.join(", ")
-					));
+mod bench;
+mod diagnostics;
+mod grid;
+
+pub use bench::{Bench, Stats};
+pub use diagnostics::{Diagnostic, Label, ParseError};
+pub use grid::{BoundingBox, Grid, Point};
+
+use anyhow::bail;
+use chumsky::prelude::*;
+use paste::paste;
+use serde::{
+	de::{self, Visitor},
+	Deserialize, Deserializer
+};
+use std::{
+	borrow::Cow,
+	convert::Infallible,
+	fmt::{self, Display, Formatter},
+	fs, io,
+	path::PathBuf,
+	str::FromStr
+};
+
+/// Where to read the puzzle input from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InputSource {
+	File(PathBuf),
+	Stdin,
+	Text(String)
+}
+
+impl InputSource {
+	/// The name of this input, used when reporting errors.
+	pub fn name(&self) -> Cow<'_, str> {
+		match self {
+			Self::File(path) => path.to_string_lossy(),
+			Self::Stdin => "<stdin>".into(),
+			Self::Text(_) => "<text>".into()
+		}
+	}
+
+	pub fn read_to_string(&self) -> io::Result<String> {
+		match self {
+			Self::File(path) => fs::read_to_string(path),
+			Self::Stdin => io::read_to_string(io::stdin()),
+			Self::Text(text) => Ok(text.clone())
+		}
+	}
+}
+
+impl Display for InputSource {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.name())
+	}
+}
+
+/// Parses a command line argument: `-` means stdin, anything else is a path.
+impl FromStr for InputSource {
+	type Err = Infallible;
+
+	fn from_str(arg: &str) -> Result<Self, Infallible> {
+		Ok(match arg {
+			"-" => Self::Stdin,
+			path => Self::File(path.into())
+		})
+	}
+}
+
+impl From<PathBuf> for InputSource {
+	fn from(path: PathBuf) -> Self {
+		Self::File(path)
+	}
+}
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Answer {
+	Number(i128),
+	Text(String)
+}
+
+impl Display for Answer {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Number(num) => write!(f, "{num}"),
+			Self::Text(text) => f.write_str(text)
+		}
+	}
+}
+
+macro_rules! answer_from_number {
+	($($ty:ty),*) => {
+		$(
+			impl From<$ty> for Answer {
+				fn from(num: $ty) -> Self {
+					Self::Number(num as i128)
 				}
-			},
-
-			(SimpleReason::Unexpected, None) => {
-				report.set_message("Unexpected end of file");
-			},
-
-			(SimpleReason::Unclosed { span, delimiter }, found) => {
-				report.set_message("Unclosed delimiter");
-				report.add_label(
-					Label::new((path_str, span.clone()))
-						.with_message(format!("Unclosed delimiter {}", delimiter))
-				);
-				if let Some(found) = found {
-					report.add_label(
-						Label::new((path_str, e.span()))
-							.with_message(format!("Must be closed before this {found}"))
-					);
+			}
+		)*
+	};
+}
+
+answer_from_number!(i32, i64, i128, u32, u64, usize);
+
+struct AnswerVisitor;
+
+impl Visitor<'_> for AnswerVisitor {
+	type Value = Answer;
+
+	fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str("a number or a string")
+	}
+
+	fn visit_i64<E: de::Error>(self, num: i64) -> Result<Answer, E> {
+		Ok(num.into())
+	}
+
+	fn visit_u64<E: de::Error>(self, num: u64) -> Result<Answer, E> {
+		Ok(num.into())
+	}
+
+	fn visit_i128<E: de::Error>(self, num: i128) -> Result<Answer, E> {
+		Ok(num.into())
+	}
+
+	fn visit_str<E: de::Error>(self, text: &str) -> Result<Answer, E> {
+		Ok(text.into())
+	}
+}
+
+impl<'de> Deserialize<'de> for Answer {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_any(AnswerVisitor)
+	}
+}
+
+impl From<String> for Answer {
+	fn from(text: String) -> Self {
+		Self::Text(text)
+	}
+}
+
+impl From<&str> for Answer {
+	fn from(text: &str) -> Self {
+		Self::Text(text.to_owned())
+	}
+}
+
+/// Whether we are solving the example from the puzzle statement or the real puzzle.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Puzzle {
+	Example,
+	#[default]
+	Real
+}
+
+impl Puzzle {
+	/// The name of the input file of this puzzle inside the directory of the day.
+	pub fn file_name(self) -> &'static str {
+		match self {
+			Self::Example => "example.txt",
+			Self::Real => "input.txt"
+		}
+	}
+
+	/// The parameters of a solution for this puzzle.
+	pub fn params<S: Solution>(self) -> S::Params {
+		match self {
+			Self::Example => S::EXAMPLE,
+			Self::Real => S::REAL
+		}
+	}
+}
+
+/// The solution of one day of the Advent of Code.
+pub trait Solution {
+	type Input;
+
+	/// Parameters that are different for the example from the puzzle statement, like
+	/// the row to look at or the size of the map.
+	type Params;
+
+	/// The parameters for the example from the puzzle statement.
+	const EXAMPLE: Self::Params;
+
+	/// The parameters for the real puzzle input.
+	const REAL: Self::Params;
+
+	/// Read and parse the puzzle input.
+	fn parse(input: &InputSource) -> anyhow::Result<Self::Input>;
+
+	fn part1(input: &Self::Input, params: &Self::Params) -> anyhow::Result<Answer>;
+
+	fn part2(input: &Self::Input, params: &Self::Params) -> anyhow::Result<Answer>;
+}
+
+fn run_solution<S: Solution>(
+	part: Option<u8>,
+	input: &InputSource,
+	puzzle: Puzzle
+) -> anyhow::Result<Vec<(u8, Answer)>> {
+	let input = S::parse(input)?;
+	let params = puzzle.params::<S>();
+	let mut answers = Vec::new();
+	if part != Some(2) {
+		answers.push((1, S::part1(&input, &params)?));
+	}
+	if part != Some(1) {
+		answers.push((2, S::part2(&input, &params)?));
+	}
+	Ok(answers)
+}
+
+macro_rules! days {
+	($($day:literal),*) => {
+		paste! {
+			$(pub mod [<day $day>];)*
+
+			/// All days that have a solution written in Rust.
+			pub const DAYS: &[u8] = &[$($day),*];
+
+			/// Run the solution of a day and return the answer of each part that was run.
+			/// If no part is given, both parts are run.
+			pub fn run(
+				day: u8,
+				part: Option<u8>,
+				input: &InputSource,
+				puzzle: Puzzle
+			) -> anyhow::Result<Vec<(u8, Answer)>> {
+				match day {
+					$($day => run_solution::<[<day $day>]::[<Day $day>]>(
+						part,
+						input,
+						puzzle
+					),)*
+					_ => bail!("Day {day} has no solution written in Rust")
 				}
-			},
+			}
 
-			(SimpleReason::Custom(msg), _) => {
-				report.set_message(msg);
-				report.add_label(Label::new((path_str, e.span())).with_message(msg));
+			/// Time the solution of a day over several runs. If no part is given, both
+			/// parts are timed.
+			pub fn bench(
+				day: u8,
+				part: Option<u8>,
+				runs: usize,
+				input: &InputSource,
+				puzzle: Puzzle
+			) -> anyhow::Result<Bench> {
+				match day {
+					$($day => bench::bench_solution::<[<day $day>]::[<Day $day>]>(
+						day,
+						part,
+						runs,
+						input,
+						puzzle
+					),)*
+					_ => bail!("Day {day} has no solution written in Rust")
+				}
 			}
-		};
-		report
-			.finish()
-			.print((path_str, Source::from(buf)))
-			.unwrap()
-	}
+		}
+	};
+}
+
+days!(9, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22);
+
+/// Parts whose solution never finishes, as `(day, part)`. Timing all days skips them.
+pub const NON_TERMINATING: &[(u8, u8)] = &[(17, 2)];
+
+/// Log to stderr, so that debug output doesn't get mixed up with the answers. The filter
+/// uses the `env_logger` syntax, like `debug` or `warn,day17=trace`. The log target of
+/// every day is its module, which can be abbreviated to just `dayN`.
+pub fn init_logging(filter: &str) {
+	let filter = filter
+		.split(',')
+		.map(|directive| match directive.starts_with("day") {
+			true => Cow::Owned(format!("aoc22::{directive}")),
+			false => Cow::Borrowed(directive)
+		})
+		.collect::<Vec<_>>()
+		.join(",");
+	env_logger::Builder::new()
+		.parse_filters(&filter)
+		.format_timestamp(None)
+		.init();
+}
+
+/// Parse the input one line at a time. If a line fails to parse, the error is recorded
+/// and parsing continues with the next line.
+pub fn lines<P, T>(line: P) -> impl Parser<char, Vec<T>, Error = Simple<char>>
+where
+	P: Parser<char, T, Error = Simple<char>>
+{
+	line.then_ignore(just('\n'))
+		.map(Some)
+		.recover_with(skip_until(['\n'], |_| None).consume_end())
+		.repeated()
+		.at_least(1)
+		.map(|lines| lines.into_iter().flatten().collect())
+}
+
+/// The result of parsing an input with error recovery.
+#[derive(Debug)]
+pub struct Partial<T> {
+	/// The parsed value. This is present if the parser was able to recover from all
+	/// errors, and then only contains the records that were parsed successfully.
+	pub value: Option<T>,
+	/// All errors that were encountered while parsing.
+	pub errors: Option<ParseError>
+}
+
+/// Read the input and parse it, recovering from as many errors as possible.
+pub fn read_partial<C, T>(input: &InputSource, parser: C) -> io::Result<Partial<T>>
+where
+	C: Parser<char, T, Error = Simple<char>>
+{
+	let buf = input.read_to_string()?;
+	let (value, errors) = parser.parse_recovery(buf.as_str());
+	let errors = (!errors.is_empty())
+		.then(|| ParseError::new(input.name().into_owned(), buf, errors));
+	Ok(Partial { value, errors })
 }
 
-pub fn read<P, C, T>(path: P, parser: C) -> anyhow::Result<T>
+/// Read the input and parse it. If parsing fails, the returned error is a [`ParseError`]
+/// containing all errors that were encountered.
+pub fn read<C, T>(input: &InputSource, parser: C) -> anyhow::Result<T>
 where
-	P: AsRef<Path>,
 	C: Parser<char, T, Error = Simple<char>>
 {
-	let path = path.as_ref();
-	let buf = fs::read_to_string(path)?;
-	parser.parse(buf.as_str()).map_err(|errors| {
-		report_err(&buf, &path.to_string_lossy(), errors);
-		anyhow!("Failed to parse input")
-	})
+	match read_partial(input, parser)? {
+		Partial {
+			value: Some(value),
+			errors: None
+		} => Ok(value),
+		Partial { errors, .. } => Err(errors
+			.expect("parser failed without emitting errors")
+			.into())
+	}
+}
+
+/// Read the input and parse it like [`read`], then check the parsed value. The check
+/// gets the text of the input and returns all errors it found, which are reported as a
+/// [`ParseError`] just like syntax errors.
+pub fn read_validated<C, T, F>(
+	input: &InputSource,
+	parser: C,
+	validate: F
+) -> anyhow::Result<T>
+where
+	C: Parser<char, T, Error = Simple<char>>,
+	F: FnOnce(&str, &T) -> Vec<Diagnostic>
+{
+	let text = input.read_to_string()?;
+	let (value, errors) = parser.parse_recovery(text.as_str());
+	let name = input.name().into_owned();
+	match value {
+		Some(value) if errors.is_empty() => {
+			let diagnostics = validate(&text, &value);
+			if diagnostics.is_empty() {
+				return Ok(value);
+			}
+			Err(ParseError {
+				name,
+				text,
+				diagnostics
+			}
+			.into())
+		},
+		_ => Err(ParseError::new(name, text, errors).into())
+	}
 }
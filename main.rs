@@ -0,0 +1,547 @@
+use anyhow::bail;
+use aoc22::{
+	day20::{trace::Trace, Day20, Decryption, IntKind},
+	day21::{self, dot, repl::Repl, Day21},
+	day22::{
+		self, cube::Cube, debugger::Debugger, render, topology::TopologyKind,
+		walker::Walker, Day22
+	},
+	InputSource, ParseError, Puzzle, Solution
+};
+use clap::{
+	parser::ValueSource, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum
+};
+use std::{
+	fs, io,
+	path::{Path, PathBuf},
+	process::ExitCode
+};
+
+#[derive(Parser)]
+#[command(about = "Solutions to the Advent of Code 2022")]
+struct Args {
+	/// How to report errors in the puzzle input.
+	#[arg(long, value_enum, default_value_t = DiagnosticsFormat::Fancy, global = true)]
+	diagnostics: DiagnosticsFormat,
+
+	/// Which log messages to print to stderr, like `debug` or `warn,day17=trace`.
+	#[arg(long, env = "AOC_LOG", default_value = "warn", global = true)]
+	log: String,
+
+	#[command(subcommand)]
+	command: Command
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DiagnosticsFormat {
+	/// Pretty terminal output.
+	Fancy,
+	/// One `path:line:column: message` line per error.
+	Plain,
+	/// Machine-readable JSON.
+	Json
+}
+
+impl DiagnosticsFormat {
+	fn report(self, err: &ParseError) {
+		match self {
+			Self::Fancy => err.write_fancy(io::stderr()).unwrap(),
+			Self::Plain => eprint!("{}", err.to_plain()),
+			Self::Json => eprintln!("{}", err.to_json())
+		}
+	}
+}
+
+#[derive(Subcommand)]
+enum Command {
+	/// Run the solution of a day.
+	Run {
+		/// The day to run.
+		day: u8,
+
+		/// Only run this part. Runs both parts if not specified.
+		#[arg(short, long, value_parser = clap::value_parser!(u8).range(1 ..= 2))]
+		part: Option<u8>,
+
+		#[command(flatten)]
+		input: PuzzleInput
+	},
+
+	/// Time the parsing and both parts of a day's solution over several runs.
+	Bench {
+		/// The day to time. Times all days if not specified, skipping parts that never
+		/// finish like part 2 of day 17.
+		day: Option<u8>,
+
+		/// Only time this part. Times both parts if not specified.
+		#[arg(short, long, value_parser = clap::value_parser!(u8).range(1 ..= 2))]
+		part: Option<u8>,
+
+		#[command(flatten)]
+		input: PuzzleInput,
+
+		/// How often to run each step.
+		#[arg(short = 'n', long, default_value_t = 5, value_parser = clap::value_parser!(u64).range(1 ..))]
+		runs: u64,
+
+		/// Write the results as JSON to this file.
+		#[arg(short, long)]
+		output: Option<PathBuf>
+	},
+
+	/// Tools for playing with the decryption on day 20.
+	Day20 {
+		#[command(subcommand)]
+		command: Day20Command
+	},
+
+	/// Tools for looking at the monkeys on day 21.
+	Day21 {
+		#[command(subcommand)]
+		command: Day21Command
+	},
+
+	/// Tools for looking at the walk on day 22.
+	Day22 {
+		#[command(subcommand)]
+		command: Day22Command
+	}
+}
+
+#[derive(Subcommand)]
+enum Day20Command {
+	/// Decrypt the numbers with custom settings. The defaults are those of part 2.
+	Decrypt {
+		#[command(flatten)]
+		input: PuzzleInput,
+
+		/// Multiply every number by this key before mixing.
+		#[arg(
+			short,
+			long,
+			default_value_t = 811589153,
+			allow_negative_numbers = true
+		)]
+		key: i128,
+
+		/// How often to mix the numbers.
+		#[arg(short, long, default_value_t = 10)]
+		rounds: usize,
+
+		/// The number to count the offsets from, as it appears in the input.
+		#[arg(short, long, default_value_t = 0, allow_negative_numbers = true)]
+		anchor: i128,
+
+		/// The positions after the anchor whose values are added up.
+		#[arg(short, long, value_delimiter = ',', default_values_t = [1000, 2000, 3000])]
+		offsets: Vec<usize>,
+
+		/// The integer type to calculate with. Use `i128` if the key is so large that
+		/// `i64` overflows.
+		#[arg(long, value_enum, default_value_t = IntKind::I64)]
+		int: IntKind
+	},
+
+	/// Record every move made while mixing, one `value from to` per line. Positions
+	/// are counted from the start of the list, like in the puzzle statement.
+	Trace {
+		#[command(flatten)]
+		input: PuzzleInput,
+
+		/// Multiply every number by this key before mixing. Part 2 uses 811589153.
+		#[arg(short, long, default_value_t = 1, allow_negative_numbers = true)]
+		key: i128,
+
+		/// How often to mix the numbers. Part 2 mixes 10 times.
+		#[arg(short, long, default_value_t = 1)]
+		rounds: usize,
+
+		/// Write the trace to this file instead of stdout.
+		#[arg(short, long)]
+		output: Option<PathBuf>
+	},
+
+	/// Replay a trace, check that every move picks up the value it claims to, and
+	/// compare it against the moves we make.
+	Replay {
+		/// The trace to replay, or `-` to read from stdin.
+		trace: InputSource,
+
+		#[command(flatten)]
+		input: PuzzleInput,
+
+		/// The key the numbers were multiplied with before mixing.
+		#[arg(short, long, default_value_t = 1, allow_negative_numbers = true)]
+		key: i128
+	}
+}
+
+#[derive(Subcommand)]
+enum Day21Command {
+	/// Draw the monkeys as a graph in the DOT language, with the path to the unknown
+	/// highlighted and everything that doesn't depend on it collapsed into its value.
+	Dot {
+		#[command(flatten)]
+		input: PuzzleInput,
+
+		/// The monkey to start from.
+		#[arg(short, long, default_value = "root")]
+		root: String,
+
+		/// The monkey whose value is unknown, like in part 2.
+		#[arg(short, long, default_value = "humn")]
+		unknown: String,
+
+		/// Draw the expression of the root monkey as a tree instead of the monkeys it
+		/// waits for.
+		#[arg(long)]
+		expr: bool,
+
+		/// Write the graph to this file instead of stdout.
+		#[arg(short, long)]
+		output: Option<PathBuf>
+	},
+
+	/// Explore the monkeys interactively, reading commands from stdin.
+	Repl {
+		#[command(flatten)]
+		input: PuzzleInput
+	}
+}
+
+#[derive(Subcommand)]
+enum Day22Command {
+	/// Follow the instructions on some surface and print the final password.
+	Walk {
+		/// The surface to walk on. Part 1 uses `plain` and part 2 uses `cube`.
+		#[arg(short, long, value_enum, default_value_t = TopologyKind::Cube)]
+		topology: TopologyKind,
+
+		#[command(flatten)]
+		input: PuzzleInput
+	},
+
+	/// Step through the instructions interactively, reading commands from stdin.
+	Debug {
+		/// The surface to walk on. Part 1 uses `plain` and part 2 uses `cube`.
+		#[arg(short, long, value_enum, default_value_t = TopologyKind::Cube)]
+		topology: TopologyKind,
+
+		#[command(flatten)]
+		input: PuzzleInput
+	},
+
+	/// Draw the path taken through the map, like in the puzzle statement.
+	Trace {
+		/// The surface to walk on. Part 1 uses `plain` and part 2 uses `cube`.
+		#[arg(short, long, value_enum, default_value_t = TopologyKind::Cube)]
+		topology: TopologyKind,
+
+		#[command(flatten)]
+		input: PuzzleInput,
+
+		/// Draw every face of the cube on its own instead of the whole net.
+		#[arg(short, long)]
+		faces: bool,
+
+		/// Write the drawing to this file instead of stdout.
+		#[arg(short, long)]
+		output: Option<PathBuf>
+	}
+}
+
+/// Where to read the puzzle input from.
+#[derive(clap::Args)]
+struct PuzzleInput {
+	/// Use the example from the puzzle statement instead of the real puzzle.
+	#[arg(short, long)]
+	example: bool,
+
+	/// The puzzle input, or `-` to read from stdin. Defaults to the `input.txt` of the
+	/// day, or its `example.txt` when using the example.
+	#[arg(short, long, env = "AOC_INPUT")]
+	input: Option<InputSource>
+}
+
+impl PuzzleInput {
+	fn puzzle(&self) -> Puzzle {
+		match self.example {
+			true => Puzzle::Example,
+			false => Puzzle::Real
+		}
+	}
+
+	fn source(&self, day: u8) -> InputSource {
+		self.input.clone().unwrap_or_else(|| {
+			Path::new(env!("CARGO_MANIFEST_DIR"))
+				.join(format!("day{day}"))
+				.join(self.puzzle().file_name())
+				.into()
+		})
+	}
+}
+
+/// The parts of a day to time when timing all days, leaving out the parts that never
+/// finish. Returns `None` if no part is left.
+fn terminating_parts(day: u8, part: Option<u8>) -> Option<Option<u8>> {
+	let terminates = |part| !aoc22::NON_TERMINATING.contains(&(day, part));
+	match part {
+		Some(part) => terminates(part).then_some(Some(part)),
+		None => match (terminates(1), terminates(2)) {
+			(true, true) => Some(None),
+			(true, false) => Some(Some(1)),
+			(false, true) => Some(Some(2)),
+			(false, false) => None
+		}
+	}
+}
+
+fn run(command: Command) -> anyhow::Result<()> {
+	match command {
+		Command::Run { day, part, input } => {
+			for (part, answer) in
+				aoc22::run(day, part, &input.source(day), input.puzzle())?
+			{
+				println!("Part {part}: {answer}");
+			}
+		},
+
+		Command::Bench {
+			day,
+			part,
+			input,
+			runs,
+			output
+		} => {
+			let days = match day {
+				Some(day) => vec![(day, part)],
+				None if input.input.is_some() => {
+					bail!("An input can only be given when timing a single day")
+				},
+				None => aoc22::DAYS
+					.iter()
+					.filter_map(|&day| Some((day, terminating_parts(day, part)?)))
+					.collect()
+			};
+			let mut results = Vec::new();
+			for (day, part) in days {
+				let source = input.source(day);
+				let bench =
+					aoc22::bench(day, part, runs as usize, &source, input.puzzle())?;
+				println!("{bench}");
+				results.push(bench);
+			}
+			if let Some(output) = output {
+				fs::write(output, serde_json::to_string_pretty(&results)?)?;
+			}
+		},
+
+		Command::Day20 { command } => day20(command)?,
+
+		Command::Day21 { command } => day21(command)?,
+
+		Command::Day22 { command } => day22(command)?
+	}
+
+	Ok(())
+}
+
+fn day20(command: Day20Command) -> anyhow::Result<()> {
+	match command {
+		Day20Command::Decrypt {
+			input,
+			key,
+			rounds,
+			anchor,
+			offsets,
+			int
+		} => {
+			let input = input.source(20);
+			let values = Day20::parse(&input)?;
+			let decryption = Decryption {
+				key,
+				rounds,
+				anchor,
+				offsets
+			};
+			println!("{}", int.decrypt(&values, &decryption)?);
+		},
+
+		Day20Command::Trace {
+			input,
+			key,
+			rounds,
+			output
+		} => {
+			let input = input.source(20);
+			let values = Day20::parse(&input)?;
+			let decryption = Decryption {
+				key,
+				rounds,
+				..Decryption::part1()
+			};
+			let mut list = decryption.list::<i128>(&values)?.traced();
+			for _ in 0 .. rounds {
+				list.mix();
+			}
+			let trace = list.trace().unwrap().to_string();
+			match output {
+				Some(output) => fs::write(output, trace)?,
+				None => print!("{trace}")
+			}
+		},
+
+		Day20Command::Replay { trace, input, key } => {
+			let input = input.source(20);
+			let values = Day20::parse(&input)?;
+			let trace = Trace::<i128>::parse(&trace)?;
+			let decryption = Decryption {
+				key,
+				..Decryption::part1()
+			};
+			let mut list = decryption.list::<i128>(&values)?;
+			trace.replay(list.values())?;
+
+			// mix as often as needed to make as many moves as the trace
+			list = list.traced();
+			loop {
+				let made = list.trace().unwrap().moves.len();
+				if made >= trace.moves.len() {
+					break;
+				}
+				list.mix();
+				if list.trace().unwrap().moves.len() == made {
+					bail!(
+						"The trace has {} moves, but mixing makes no moves at all",
+						trace.moves.len()
+					);
+				}
+			}
+			let ours = &list.trace().unwrap().moves;
+			if let Some(i) =
+				(0 .. trace.moves.len()).find(|i| trace.moves[*i] != ours[*i])
+			{
+				bail!(
+					"Move {} is {}, but we move {}",
+					i + 1,
+					trace.moves[i],
+					ours[i]
+				);
+			}
+			println!("All {} moves match", trace.moves.len());
+		}
+	}
+
+	Ok(())
+}
+
+fn day21(command: Day21Command) -> anyhow::Result<()> {
+	match command {
+		Day21Command::Dot {
+			input,
+			root,
+			unknown,
+			expr,
+			output
+		} => {
+			let input = input.source(21);
+			let exprs = Day21::parse(&input)?;
+			let graph = match expr {
+				true => {
+					let exprs = day21::partially_evaluate(&exprs, &root, &unknown)?;
+					dot::expr_to_dot(&exprs, &unknown)
+				},
+				false => dot::monkeys_to_dot(&exprs, &root, &unknown)?
+			};
+			match output {
+				Some(output) => fs::write(output, graph)?,
+				None => print!("{graph}")
+			}
+		},
+
+		Day21Command::Repl { input } => {
+			let input = input.source(21);
+			if matches!(input, InputSource::Stdin) {
+				bail!(
+					"The REPL reads its commands from stdin, so the input must be a file"
+				);
+			}
+			let exprs = Day21::parse(&input)?;
+			Repl::new(exprs).repl(io::stdin().lock(), io::stdout())?;
+		}
+	}
+
+	Ok(())
+}
+
+fn day22(command: Day22Command) -> anyhow::Result<()> {
+	match command {
+		Day22Command::Walk { topology, input } => {
+			let input = input.source(22);
+			let (map, instructions) = Day22::parse(&input)?;
+			let topology = topology.build(&map)?;
+			println!("{}", day22::walk(&map, &instructions, &*topology));
+		},
+
+		Day22Command::Debug { topology, input } => {
+			let input = input.source(22);
+			if matches!(input, InputSource::Stdin) {
+				bail!("The debugger reads its commands from stdin, so the input must be a file");
+			}
+			let (map, instructions) = Day22::parse(&input)?;
+			let topology = topology.build(&map)?;
+			let walker = Walker::new(&map, &instructions, &*topology);
+			Debugger::new(walker).repl(io::stdin().lock(), io::stdout())?;
+		},
+
+		Day22Command::Trace {
+			topology,
+			input,
+			faces,
+			output
+		} => {
+			let input = input.source(22);
+			let (map, instructions) = Day22::parse(&input)?;
+			let topology = topology.build(&map)?;
+			let trail = day22::trail(&map, &instructions, &*topology);
+			let drawing = match faces {
+				true => render::render_faces(&map, &Cube::fold(&map)?, &trail),
+				false => render::render_net(&map, &trail)
+			};
+			match output {
+				Some(output) => fs::write(output, drawing)?,
+				None => print!("{drawing}")
+			}
+		}
+	}
+
+	Ok(())
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+	let matches = Args::command().get_matches();
+	let mut args = Args::from_arg_matches(&matches)?;
+	// timing all days uses the input of every day, even if AOC_INPUT is set
+	if let Command::Bench {
+		day: None, input, ..
+	} = &mut args.command
+	{
+		let source = matches
+			.subcommand_matches("bench")
+			.and_then(|bench| bench.value_source("input"));
+		if source == Some(ValueSource::EnvVariable) {
+			input.input = None;
+		}
+	}
+	aoc22::init_logging(&args.log);
+	match run(args.command) {
+		Ok(()) => Ok(ExitCode::SUCCESS),
+		Err(err) => match err.downcast_ref::<ParseError>() {
+			Some(err) => {
+				args.diagnostics.report(err);
+				Ok(ExitCode::FAILURE)
+			},
+			None => Err(err)
+		}
+	}
+}
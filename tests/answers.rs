@@ -0,0 +1,164 @@
+use aoc22::{Answer, InputSource, Puzzle, Solution};
+use paste::paste;
+use serde::Deserialize;
+use std::{fs, path::PathBuf};
+
+/// The answers we know about for one part of a puzzle.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Expected {
+	/// The answer that was accepted.
+	correct: Option<Answer>,
+	/// Answers that were rejected.
+	wrong: Vec<Answer>,
+	/// Answers that were rejected for being too high.
+	too_high: Vec<i64>,
+	/// Answers that were rejected for being too low.
+	too_low: Vec<i64>
+}
+
+impl Expected {
+	fn check(&self, answer: &Answer) {
+		assert!(
+			!self.wrong.contains(answer),
+			"{answer} is known to be wrong"
+		);
+		if let Answer::Number(num) = answer {
+			for too_high in &self.too_high {
+				assert!(
+					*num < *too_high as i128,
+					"{answer} is wrong, {too_high} is too high"
+				);
+			}
+			for too_low in &self.too_low {
+				assert!(
+					*num > *too_low as i128,
+					"{answer} is wrong, {too_low} is too low"
+				);
+			}
+		}
+		if let Some(correct) = &self.correct {
+			assert_eq!(answer, correct);
+		}
+	}
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Parts {
+	part1: Expected,
+	part2: Expected
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Answers {
+	#[serde(default)]
+	part1: Expected,
+	#[serde(default)]
+	part2: Expected,
+	/// The answers to the example from the puzzle statement.
+	#[serde(default)]
+	example: Parts
+}
+
+fn check<S: Solution>(day: u8, part: u8, puzzle: Puzzle) {
+	let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(format!("day{day}"));
+	let answers: Answers =
+		toml::from_str(&fs::read_to_string(dir.join("answers.toml")).unwrap()).unwrap();
+	let answers = match puzzle {
+		Puzzle::Example => answers.example,
+		Puzzle::Real => Parts {
+			part1: answers.part1,
+			part2: answers.part2
+		}
+	};
+
+	let input = S::parse(&InputSource::File(dir.join(puzzle.file_name()))).unwrap();
+	let params = puzzle.params::<S>();
+	match part {
+		1 => answers.part1.check(&S::part1(&input, &params).unwrap()),
+		2 => answers.part2.check(&S::part2(&input, &params).unwrap()),
+		_ => unreachable!()
+	}
+}
+
+macro_rules! tests {
+	($($(#[$attr:meta])* $day:literal $puzzle:ident part $part:literal),*) => {
+		paste! {
+			$(
+				#[test]
+				$(#[$attr])*
+				fn [<day $day _ $puzzle:lower _part $part>]() {
+					check::<aoc22::[<day $day>]::[<Day $day>]>(
+						$day,
+						$part,
+						Puzzle::$puzzle
+					);
+				}
+			)*
+		}
+	};
+}
+
+tests! {
+	9 Real part 1,
+	9 Real part 2,
+	12 Real part 1,
+	12 Real part 2,
+	13 Real part 1,
+	13 Real part 2,
+	14 Real part 1,
+	14 Real part 2,
+	15 Real part 1,
+	#[ignore = "slow"]
+	15 Real part 2,
+	#[ignore = "slow"]
+	16 Real part 1,
+	#[ignore = "slow"]
+	16 Real part 2,
+	17 Real part 1,
+	#[ignore = "does not terminate"]
+	17 Real part 2,
+	18 Real part 1,
+	18 Real part 2,
+	#[ignore = "slow"]
+	19 Real part 1,
+	#[ignore = "slow"]
+	19 Real part 2,
+	20 Real part 1,
+	20 Real part 2,
+	21 Real part 1,
+	21 Real part 2,
+	22 Real part 1,
+	22 Real part 2,
+	9 Example part 1,
+	9 Example part 2,
+	12 Example part 1,
+	12 Example part 2,
+	13 Example part 1,
+	13 Example part 2,
+	14 Example part 1,
+	14 Example part 2,
+	15 Example part 1,
+	15 Example part 2,
+	#[ignore = "the performance hack makes the answer nondeterministic"]
+	16 Example part 1,
+	#[ignore = "the performance hack makes the answer nondeterministic"]
+	16 Example part 2,
+	17 Example part 1,
+	#[ignore = "does not terminate"]
+	17 Example part 2,
+	18 Example part 1,
+	18 Example part 2,
+	#[ignore = "slow"]
+	19 Example part 1,
+	#[ignore = "slow"]
+	19 Example part 2,
+	20 Example part 1,
+	20 Example part 2,
+	21 Example part 1,
+	21 Example part 2,
+	22 Example part 1,
+	22 Example part 2
+}
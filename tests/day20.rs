@@ -0,0 +1,215 @@
+use aoc22::{
+	day20::{
+		reference,
+		trace::{Move, Trace},
+		Decryption, List
+	},
+	InputSource, ParseError
+};
+use proptest::prelude::*;
+
+const EXAMPLE: [i64; 7] = [1, 2, -3, 3, -2, 0, 4];
+
+proptest! {
+	#[test]
+	fn mix_like_reference(
+		values in prop::collection::vec(-50_i64 .. 50, 2 .. 40),
+		key in prop::sample::select(vec![1, 7, 811589153]),
+		rounds in 1_usize .. 4
+	) {
+		let mut list = List::new(&values) * key;
+		let mut expected = reference::List::new(&values) * key;
+		for _ in 0 .. rounds {
+			list.mix();
+			expected.mix();
+			prop_assert_eq!(list.order(), expected.order());
+		}
+	}
+
+	#[test]
+	fn unmix(
+		values in prop::collection::vec(any::<i128>(), 2 .. 40),
+		rounds in 1_usize .. 4
+	) {
+		let mut list = List::new(&values);
+		for _ in 0 .. rounds {
+			list.mix();
+		}
+		for _ in 0 .. rounds {
+			list.unmix();
+		}
+		prop_assert_eq!(list.order(), (0 .. values.len()).collect::<Vec<_>>());
+	}
+}
+
+#[test]
+fn example() {
+	let mut list = List::new(&EXAMPLE);
+	list.mix();
+	let mixed: Vec<i64> = list.order().into_iter().map(|idx| EXAMPLE[idx]).collect();
+	assert_eq!(mixed, [1, 2, -3, 4, 0, 3, -2]);
+	assert_eq!(list.coordinates(0, &[1000, 2000, 3000]).unwrap(), [
+		4, -3, 2
+	]);
+	assert_eq!(list.sum(0, &[1000, 2000, 3000]).unwrap(), 3);
+	assert_eq!(list.sum(4, &[1, 2]).unwrap(), 3);
+	assert!(list.sum(5, &[1]).is_err());
+}
+
+#[test]
+fn decrypt() {
+	let part2 = Decryption::part2();
+	assert_eq!(part2.decrypt::<i64>(&EXAMPLE).unwrap(), 1623178306);
+	assert_eq!(part2.decrypt::<i128>(&EXAMPLE).unwrap(), 1623178306);
+
+	// the anchor is multiplied by the key as well
+	let decryption = Decryption {
+		anchor: 4,
+		offsets: vec![1],
+		..Decryption::part1()
+	};
+	assert_eq!(decryption.decrypt::<i64>(&EXAMPLE).unwrap(), 0);
+
+	// too large for i64, but not for i128
+	let decryption = Decryption {
+		key: 1 << 62,
+		..Decryption::part1()
+	};
+	let err = decryption.decrypt::<i64>(&EXAMPLE).unwrap_err();
+	assert_eq!(
+		err.to_string(),
+		"2 * 4611686018427387904 doesn't fit into i64"
+	);
+	// 2^62 = 4 (mod 6), so the values move just like with a key of 4
+	let same_moves = Decryption {
+		key: 4,
+		..Decryption::part1()
+	};
+	let sum = same_moves.decrypt::<i64>(&EXAMPLE).unwrap() as i128 / 4;
+	assert_eq!(decryption.decrypt::<i128>(&EXAMPLE).unwrap(), sum << 62);
+	assert!(decryption.decrypt::<i32>(&EXAMPLE).is_err());
+}
+
+/// The list after every move, from the puzzle statement.
+const STATEMENT: &str = "\
+2, 1, -3, 3, -2, 0, 4
+1, -3, 2, 3, -2, 0, 4
+1, 2, 3, -2, -3, 0, 4
+1, 2, -2, -3, 0, 3, 4
+1, 2, -3, 0, 3, 4, -2
+1, 2, -3, 0, 3, 4, -2
+1, 2, -3, 4, 0, 3, -2
+";
+
+/// The list after every round of part 2, from the puzzle statement.
+const STATEMENT_PART2: &str = "\
+0, -2434767459, 3246356612, -1623178306, 2434767459, 1623178306, 811589153
+0, 2434767459, 1623178306, 3246356612, -2434767459, -1623178306, 811589153
+0, 811589153, 2434767459, 3246356612, 1623178306, -1623178306, -2434767459
+0, 1623178306, -2434767459, 811589153, 2434767459, 3246356612, -1623178306
+0, 811589153, -1623178306, 1623178306, -2434767459, 3246356612, 2434767459
+0, 811589153, -1623178306, 3246356612, -2434767459, 1623178306, 2434767459
+0, -2434767459, 2434767459, 1623178306, -1623178306, 811589153, 3246356612
+0, 1623178306, 3246356612, 811589153, -2434767459, 2434767459, -1623178306
+0, 811589153, 1623178306, -2434767459, 3246356612, 2434767459, -1623178306
+0, -2434767459, 1623178306, 3246356612, -1623178306, 2434767459, 811589153
+";
+
+fn lists(text: &str) -> Vec<Vec<i64>> {
+	text.lines()
+		.map(|line| {
+			line.split(", ")
+				.map(|value| value.parse().unwrap())
+				.collect()
+		})
+		.collect()
+}
+
+#[test]
+fn trace() {
+	let mut list = List::new(&EXAMPLE).traced();
+	list.mix();
+	let trace = list.trace().unwrap();
+	assert_eq!(trace.moves[.. 2], [
+		Move {
+			value: 1,
+			from: 0,
+			to: 1
+		},
+		Move {
+			value: 2,
+			from: 0,
+			to: 2
+		}
+	]);
+	trace.verify(&EXAMPLE, &lists(STATEMENT)).unwrap();
+
+	// the trace survives being written and read back
+	let text = trace.to_string();
+	assert!(text.starts_with("1 0 1\n2 0 2\n-3 1 4\n"));
+	let parsed = Trace::parse(&InputSource::Text(text)).unwrap();
+	assert_eq!(&parsed, trace);
+
+	// a position that doesn't fit into a usize
+	let err =
+		Trace::<i64>::parse(&InputSource::Text("1 0 99999999999999999999\n".into()))
+			.unwrap_err();
+	assert_eq!(
+		err.downcast::<ParseError>().unwrap().diagnostics[0].message,
+		"Invalid position: 99999999999999999999"
+	);
+
+	// a move that picks up the wrong value
+	let mut wrong = trace.clone();
+	wrong.moves[2].from = 2;
+	let err = wrong.replay(&EXAMPLE).unwrap_err();
+	assert_eq!(
+		err.to_string(),
+		"Move 3 (-3 2 4) should move -3, but found 2 at 2"
+	);
+
+	// a move that is off by one, like mixing modulo len instead of len - 1
+	let mut wrong = trace.clone();
+	wrong.moves[4].to = 0;
+	let err = wrong.verify(&EXAMPLE, &lists(STATEMENT)).unwrap_err();
+	assert_eq!(
+		err.to_string(),
+		"After move 5 (-2 2 0), the list is [-2, 1, 2, -3, 0, 3, 4] instead of \
+		 [1, 2, -3, 0, 3, 4, -2]"
+	);
+}
+
+#[test]
+fn trace_zero_at_end() {
+	let values = [3, 1, 0];
+	let mut list = List::new(&values).traced();
+	list.mix();
+	let trace = list.trace().unwrap();
+	// the 0 stays at the end instead of jumping to the front
+	assert_eq!(trace.to_string(), "3 0 1\n1 0 1\n0 2 2\n");
+	let arrangements = trace.replay(&values).unwrap();
+	assert_eq!(arrangements.last().unwrap(), &[3, 1, 0]);
+}
+
+#[test]
+fn trace_part2() {
+	let key = 811589153;
+	let mut list = Decryption::part2().list::<i64>(&EXAMPLE).unwrap().traced();
+	for _ in 0 .. 10 {
+		list.mix();
+	}
+
+	let values: Vec<i64> = EXAMPLE.iter().map(|value| value * key).collect();
+	let arrangements = list.trace().unwrap().replay(&values).unwrap();
+	let rounds: Vec<Vec<i64>> = arrangements
+		.chunks(EXAMPLE.len())
+		.map(|round| {
+			// the statement starts every round at 0
+			let mut after = round.last().unwrap().clone();
+			let zero = after.iter().position(|value| *value == 0).unwrap();
+			after.rotate_left(zero);
+			after
+		})
+		.collect();
+	assert_eq!(rounds, lists(STATEMENT_PART2));
+}
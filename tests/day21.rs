@@ -0,0 +1,330 @@
+use aoc22::{
+	day21::{
+		self, dot,
+		repl::Repl,
+		symbolic::{self, Solutions},
+		verify, Day21, Expr, Number
+	},
+	Answer, InputSource, ParseError, Solution
+};
+use num_rational::Ratio;
+use std::{collections::HashMap, path::Path};
+
+fn int(value: i64) -> Number {
+	Number::from_integer(value.into())
+}
+
+fn parse(text: &str) -> HashMap<String, Expr> {
+	Day21::parse(&InputSource::Text(text.to_owned())).unwrap()
+}
+
+/// Solve `aaaa = bbbb` for humn.
+fn solve(text: &str) -> anyhow::Result<Solutions> {
+	solve_exprs(&parse(text))
+}
+
+fn solve_exprs(exprs: &HashMap<String, Expr>) -> anyhow::Result<Solutions> {
+	let lhs = Expr::Variable("aaaa".into());
+	let rhs = Expr::Variable("bbbb".into());
+	symbolic::solve(&lhs, &rhs, exprs, "humn")
+}
+
+#[test]
+fn humn_twice() {
+	let solutions = solve("aaaa: humn + humn\nbbbb: 10\nhumn: 1\n").unwrap();
+	assert_eq!(solutions, Solutions::One(int(5)));
+
+	let solutions =
+		solve("aaaa: humn - cccc\ncccc: humn * dddd\ndddd: 3\nbbbb: 8\nhumn: 0\n");
+	assert_eq!(solutions.unwrap(), Solutions::One(int(-4)));
+}
+
+#[test]
+fn no_or_infinite_solutions() {
+	let solutions = solve("aaaa: humn - humn\nbbbb: 0\nhumn: 0\n").unwrap();
+	assert_eq!(solutions, Solutions::Infinite);
+	let solutions = solve("aaaa: humn - humn\nbbbb: 1\nhumn: 0\n").unwrap();
+	assert_eq!(solutions, Solutions::None);
+}
+
+#[test]
+fn rational() {
+	// (humn + 1) / (humn - 1) = 3
+	let text = "aaaa: cccc / dddd\ncccc: humn + one\ndddd: humn - one\none: 1\nbbbb: 3\nhumn: 0\n";
+	assert_eq!(solve(text).unwrap(), Solutions::One(int(2)));
+
+	// humn / humn = 1 holds for every value but 0
+	let solutions = solve("aaaa: humn / humn\nbbbb: 1\nhumn: 0\n").unwrap();
+	assert_eq!(solutions, Solutions::Infinite);
+
+	// 2 / humn = 4 has no integer solution
+	let solutions = solve("aaaa: two / humn\ntwo: 2\nbbbb: 4\nhumn: 0\n").unwrap();
+	assert_eq!(solutions, Solutions::One(Ratio::new(1.into(), 2.into())));
+}
+
+#[test]
+fn not_linear() {
+	assert!(solve("aaaa: humn * humn\nbbbb: 4\nhumn: 0\n").is_err());
+}
+
+#[test]
+fn exact_division() {
+	// the old i64 arithmetic truncated 7 / 2 to 3, so that humn = 3 looked like a solution
+	let text = "root: aaaa + bbbb\naaaa: humn / two\ntwo: 2\nbbbb: 3\nhumn: 0\n";
+	let exprs = parse(text);
+	assert!(verify(&exprs, "humn", &int(7)).is_err());
+	assert!(verify(&exprs, "humn", &int(6)).is_ok());
+
+	let solutions = solve("aaaa: humn / two\ntwo: 2\nbbbb: 7\nhumn: 0\n").unwrap();
+	assert_eq!(solutions, Solutions::One(int(14)));
+	assert!(Day21::part2(&parse(text), &()).is_ok());
+
+	// 8 / 3 = humn has no integer solution, so part 2 must not round
+	let text = "root: aaaa + bbbb\naaaa: eight / three\neight: 8\nthree: 3\nbbbb: humn * one\none: 1\nhumn: 0\n";
+	assert!(Day21::part2(&parse(text), &()).is_err());
+}
+
+fn parse_err(text: &str) -> ParseError {
+	let err = Day21::parse(&InputSource::Text(text.to_owned())).unwrap_err();
+	err.downcast::<ParseError>().unwrap()
+}
+
+#[test]
+fn dangling_reference() {
+	let err = parse_err("root: aaaa + bbbb\naaaa: 1\n");
+	assert_eq!(err.diagnostics.len(), 1);
+	let d = &err.diagnostics[0];
+	assert_eq!(d.message, "Monkey root waits for an unknown monkey");
+	assert_eq!((d.line, d.column), (1, 14));
+	assert_eq!(d.labels[0].message, "There is no monkey named bbbb");
+}
+
+#[test]
+fn cycle() {
+	let err = parse_err("root: aaaa + one\none: 1\naaaa: bbbb * one\nbbbb: aaaa - one\n");
+	assert_eq!(err.diagnostics.len(), 1);
+	let d = &err.diagnostics[0];
+	assert_eq!(d.message, "Monkeys aaaa, bbbb wait for each other");
+	let labels: Vec<_> = d
+		.labels
+		.iter()
+		.map(|label| label.message.as_str())
+		.collect();
+	assert_eq!(labels, ["aaaa waits for bbbb", "bbbb waits for aaaa"]);
+	assert_eq!((d.line, d.column), (3, 7));
+
+	let err = parse_err("root: root + root\n");
+	assert_eq!(err.diagnostics[0].message, "Monkey root waits for itself");
+}
+
+#[test]
+fn defined_twice() {
+	let err = parse_err("root: 1\nroot: 2\n");
+	assert_eq!(err.diagnostics.len(), 1);
+	let d = &err.diagnostics[0];
+	assert_eq!(d.message, "Monkey root is defined twice");
+	assert_eq!((d.line, d.column), (1, 1));
+	assert_eq!(d.labels[1].message, "Defined again here");
+}
+
+#[test]
+fn deep_chain() {
+	// deep enough to overflow the stack when evaluating recursively
+	let mut text = "root: m0 + humn\none: 1\nhumn: 5\n".to_owned();
+	let depth = 100_000;
+	for i in 0 .. depth {
+		text += &format!("m{i}: m{} + one\n", i + 1);
+	}
+	text += &format!("m{depth}: 0\n");
+	let exprs = parse(&text);
+	assert_eq!(
+		Day21::part1(&exprs, &()).unwrap(),
+		Answer::Number(depth + 5)
+	);
+	assert_eq!(Day21::part2(&exprs, &()).unwrap(), Answer::Number(depth));
+}
+
+#[test]
+fn dot() {
+	let exprs = parse(
+		"root: aaaa + bbbb\naaaa: humn * two\nbbbb: two + three\ntwo: 2\nthree: 3\nhumn: 1\n"
+	);
+	let graph = dot::monkeys_to_dot(&exprs, "root", "humn").unwrap();
+	let lines: Vec<&str> = graph.lines().collect();
+	let highlight = "color=red, fontcolor=red, penwidth=2";
+	assert!(lines.contains(&format!(r#"	"root" -> "aaaa" [{highlight}];"#).as_str()));
+	assert!(lines.contains(&r#"	"root" -> "bbbb" [color=black];"#));
+	assert!(lines.contains(&format!(r#"	"aaaa" -> "humn" [{highlight}];"#).as_str()));
+	assert!(lines
+		.contains(&r#"	"bbbb" [label="bbbb\n= 5", style=filled, fillcolor=lightgray];"#));
+	// bbbb is collapsed, so three is never drawn
+	assert!(!graph.contains("three"));
+	// root, aaaa and humn, and the edges between them
+	let highlighted = lines.iter().filter(|line| line.contains(highlight));
+	assert_eq!(highlighted.count(), 5);
+
+	let partial = day21::partially_evaluate(&exprs, "root", "humn").unwrap();
+	let lines: Vec<String> = partial
+		.iter()
+		.map(|(monkey, expr)| format!("{monkey} = {expr}"))
+		.collect();
+	assert_eq!(lines, ["root = (aaaa + 5)", "aaaa = (humn * 2)"]);
+	assert_eq!(
+		dot::expr_to_dot(&partial, "humn"),
+		r#"digraph expr {
+	node [shape=box];
+	n1 [label="humn", color=red, fontcolor=red, penwidth=2];
+	n0 -> n1 [color=red, fontcolor=red, penwidth=2];
+	n2 [label="2", style=filled, fillcolor=lightgray];
+	n0 -> n2 [color=black];
+	n0 [label="*", color=red, fontcolor=red, penwidth=2];
+	n3 -> n0 [color=red, fontcolor=red, penwidth=2];
+	n4 [label="5", style=filled, fillcolor=lightgray];
+	n3 -> n4 [color=black];
+	n3 [label="+", color=red, fontcolor=red, penwidth=2];
+}
+"#
+	);
+}
+
+#[test]
+fn dot_unknown_monkey() {
+	let exprs = parse("root: humn + two\ntwo: 2\nhumn: 1\n");
+	for (root, unknown) in [("nosuch", "humn"), ("root", "nosuch")] {
+		assert_eq!(
+			dot::monkeys_to_dot(&exprs, root, unknown)
+				.unwrap_err()
+				.to_string(),
+			"There is no monkey named nosuch"
+		);
+	}
+}
+
+#[test]
+fn deep_dot() {
+	// a long chain of monkeys waiting for the unknown, which used to be inlined into
+	// one deep expression
+	let mut text = "root: m0 + one
+one: 1
+humn: 5
+"
+	.to_owned();
+	let depth = 20_000;
+	for i in 0 .. depth {
+		text += &format!("m{i}: m{} + one\n", i + 1);
+	}
+	text += &format!("m{depth}: humn * 2\n");
+	let exprs = parse(&text);
+
+	let graph = dot::monkeys_to_dot(&exprs, "root", "humn").unwrap();
+	let highlight = "color=red, fontcolor=red, penwidth=2";
+	let edge = format!(r#"	"m{}" -> "m{depth}" [{highlight}];"#, depth - 1);
+	assert!(graph.lines().any(|line| line == edge));
+
+	let partial = day21::partially_evaluate(&exprs, "root", "humn").unwrap();
+	assert_eq!(partial.len(), depth + 2);
+	assert_eq!(partial[0].1.to_string(), "(m0 + 1)");
+	assert_eq!(partial[depth + 1].1.to_string(), "(humn * 2)");
+	let graph = dot::expr_to_dot(&partial, "humn");
+	// root, the chain, its `*` and humn, and the edges between them
+	assert_eq!(graph.matches(highlight).count(), 2 * depth + 5);
+}
+
+fn part1(text: &str) -> Answer {
+	Day21::part1(&parse(text), &()).unwrap()
+}
+
+#[test]
+fn operators() {
+	assert_eq!(part1("root: 7 % 3\n"), Answer::Number(1));
+	assert_eq!(part1("root: (0 - 7) % 3\n"), Answer::Number(2));
+	assert_eq!(
+		part1("root: min(4, 2 * 3) + max(4, 2 * 3)\n"),
+		Answer::Number(10)
+	);
+	assert_eq!(
+		part1("root: (1 < 2) + (2 <= 2) + (1 > 2) + (1 >= 2)\n"),
+		Answer::Number(2)
+	);
+	assert_eq!(
+		part1("root: (1 == 1) * 10 + (1 != 1)\n"),
+		Answer::Number(10)
+	);
+	assert!(Day21::part1(&parse("root: 1 % 0\n"), &()).is_err());
+}
+
+#[test]
+fn nested() {
+	// `*` binds stronger than `+`, which binds stronger than `<`
+	assert_eq!(part1("root: 1 + 2 * 3 - 4\n"), Answer::Number(3));
+	assert_eq!(part1("root: (1 + 2) * (3 - 4)\n"), Answer::Number(-3));
+	assert_eq!(part1("root: 1 + 2 < 2 * 2\n"), Answer::Number(1));
+	assert_eq!(
+		parse("root: min(aaaa, (aaaa + 3) * bbbb)\naaaa: 2\nbbbb: aaaa\n")["root"]
+			.to_string(),
+		"min(aaaa, ((aaaa + 3) * bbbb))"
+	);
+
+	let text = "root: (aaaa + 3) * bbbb\naaaa: humn * 2\nbbbb: 10 - max(one, 3)\none: 1\nhumn: 4\n";
+	assert_eq!(part1(text), Answer::Number(77));
+	// (2 humn + 3) = 7
+	assert_eq!(Day21::part2(&parse(text), &()).unwrap(), Answer::Number(2));
+
+	let err = parse_err("root: (aaaa + 3 * bbbb\naaaa: 1\nbbbb: 2\n");
+	assert_eq!(err.diagnostics[0].line, 1);
+}
+
+#[test]
+fn not_invertible() {
+	let solutions = solve("aaaa: humn % 7\nbbbb: 3\nhumn: 0\n");
+	assert_eq!(
+		solutions.unwrap_err().to_string(),
+		"% can't be inverted, but (x) % (7) depends on the unknown"
+	);
+	assert!(solve("aaaa: min(humn, 7)\nbbbb: 3\nhumn: 0\n").is_err());
+	assert!(solve("aaaa: humn < 7\nbbbb: 1\nhumn: 0\n").is_err());
+
+	// ops that can't be inverted are fine as long as they don't depend on humn
+	let solutions = solve("aaaa: humn + min(7, 9 % 4)\nbbbb: 3\nhumn: 0\n").unwrap();
+	assert_eq!(solutions, Solutions::One(int(2)));
+}
+
+#[test]
+fn repl() {
+	let input = Path::new(env!("CARGO_MANIFEST_DIR")).join("day21/example.txt");
+	let mut repl = Repl::new(Day21::parse(&input.into()).unwrap());
+	let mut run = |command: &str| {
+		let mut out = Vec::new();
+		repl.command(command, &mut out)?;
+		Ok::<_, anyhow::Error>(String::from_utf8(out).unwrap())
+	};
+
+	assert_eq!(run("value root").unwrap(), "root = 152\n");
+	assert_eq!(
+		run("show pppw").unwrap(),
+		"pppw = (cczh / 4)\ncczh = (4 + lgvd)\nlgvd = (2 * ptdq)\nptdq = (humn - 3)\n"
+	);
+	assert_eq!(run("solve humn").unwrap(), "humn = 301\n");
+	assert_eq!(run("solve humn pppw = 2 * sjmn").unwrap(), "humn = 601\n");
+	assert_eq!(
+		run("solve humn sjmn = 150").unwrap(),
+		"Found infinitely many solutions for humn\n"
+	);
+
+	assert_eq!(run("set humn 301").unwrap(), "humn: 301\n");
+	assert_eq!(run("value root").unwrap(), "root = 300\n");
+	assert_eq!(run("set sjmn pppw + 1").unwrap(), "sjmn: (pppw + 1)\n");
+	assert_eq!(run("value root").unwrap(), "root = 301\n");
+	assert_eq!(
+		run("set pppw sjmn").unwrap_err().to_string(),
+		"Monkeys pppw, sjmn would wait for each other"
+	);
+	assert_eq!(run("value pppw").unwrap(), "pppw = 150\n");
+	assert!(run("set pppw nobody").is_err());
+	assert!(run("set pppw (1 +").is_err());
+	assert!(run("reset").unwrap().is_empty());
+	assert_eq!(run("value root").unwrap(), "root = 152\n");
+
+	let mut out = Vec::new();
+	assert!(!repl.command("quit", &mut out).unwrap());
+}
@@ -0,0 +1,338 @@
+use aoc22::{
+	day22::{
+		self,
+		cube::Cube,
+		debugger::Debugger,
+		render,
+		topology::{Clamped, Klein, Plain, Topology, Torus},
+		walker::{Event, Walker},
+		Day22, Facing, Instruction, Map
+	},
+	InputSource, ParseError, Point, Solution
+};
+use proptest::prelude::*;
+use std::{iter, path::Path};
+
+/// All 11 nets of a cube, one character per face.
+const NETS: [&str; 11] = [
+	"#...\n####\n#...",
+	"#...\n####\n.#..",
+	"#...\n####\n..#.",
+	"#...\n####\n...#",
+	".#..\n####\n.#..",
+	".#..\n####\n..#.",
+	"##..\n.###\n.#..",
+	"##..\n.###\n..#.",
+	"##..\n.###\n...#",
+	"##..\n.##.\n..##",
+	"###..\n..###"
+];
+
+const FACINGS: [Facing; 4] = [Facing::Right, Facing::Down, Facing::Left, Facing::Up];
+
+/// Scale a net up to faces of the given size, and return the text of the map together
+/// with all its tiles.
+fn net_text(net: &str, size: usize) -> (String, Vec<Point>) {
+	let mut text = String::new();
+	let mut tiles = Vec::new();
+	for (fy, line) in net.lines().enumerate() {
+		for dy in 0 .. size {
+			let y = fy * size + dy;
+			let row: String = line
+				.chars()
+				.flat_map(|face| iter::repeat_n(face, size))
+				.map(|face| match face {
+					'#' => '.',
+					_ => ' '
+				})
+				.collect::<String>()
+				.trim_end()
+				.to_owned();
+			for (x, _) in row.char_indices().filter(|(_, tile)| *tile == '.') {
+				tiles.push(Point::new(x as i64, y as i64));
+			}
+			text += &row;
+			text += "\n";
+		}
+	}
+	(text, tiles)
+}
+
+/// Scale a net up to faces of the given size, and return the map together with all its
+/// tiles.
+fn net_map(net: &str, size: usize) -> (Map, Vec<Point>) {
+	let (text, tiles) = net_text(net, size);
+	let (map, _) = Day22::parse(&InputSource::Text(text + "\n0\n")).unwrap();
+	(map, tiles)
+}
+
+proptest! {
+	#[test]
+	fn step_back(
+		net in 0 .. NETS.len(),
+		size in 1_usize .. 6,
+		tile in any::<prop::sample::Index>(),
+		facing in prop::sample::select(&FACINGS[..])
+	) {
+		let (map, tiles) = net_map(NETS[net], size);
+		let cube = Cube::fold(&map).unwrap();
+		let start = tiles[tile.index(tiles.len())];
+
+		let (pos, facing_there) = cube.step(start, facing);
+		prop_assert!(tiles.contains(&pos));
+		let (back, facing_back) = cube.step(pos, facing_there.opposite());
+		prop_assert_eq!(back, start);
+		prop_assert_eq!(facing_back.opposite(), facing);
+	}
+
+	#[test]
+	fn walk_around(
+		net in 0 .. NETS.len(),
+		size in 1_usize .. 6,
+		tile in any::<prop::sample::Index>(),
+		facing in prop::sample::select(&FACINGS[..])
+	) {
+		let (map, tiles) = net_map(NETS[net], size);
+		let cube = Cube::fold(&map).unwrap();
+		let start = tiles[tile.index(tiles.len())];
+
+		let mut pos = start;
+		let mut facing_now = facing;
+		for _ in 0 .. 4 * size {
+			(pos, facing_now) = cube.step(pos, facing_now);
+		}
+		prop_assert_eq!(pos, start);
+		prop_assert_eq!(facing_now, facing);
+	}
+
+	#[test]
+	fn jump_table(
+		net in 0 .. NETS.len(),
+		size in 1_usize .. 6,
+		walls in prop::collection::vec(prop::bool::weighted(0.2), 150),
+		moves in prop::collection::vec((0_usize .. 40, any::<bool>()), 1 .. 20)
+	) {
+		let (mut text, tiles) = net_text(NETS[net], size);
+		// keep the start open
+		for (i, tile) in tiles.iter().enumerate().skip(1) {
+			if walls[i] {
+				let offset = text.lines().take(tile.y as usize).map(|line| line.len() + 1).sum::<usize>();
+				let offset = offset + tile.x as usize;
+				text.replace_range(offset ..= offset, "#");
+			}
+		}
+		text += "\n";
+		for (steps, clockwise) in moves {
+			text += &format!("{steps}{}", if clockwise { 'R' } else { 'L' });
+		}
+		text += "\n";
+		let (map, instructions) = Day22::parse(&InputSource::Text(text)).unwrap();
+
+		let cube = Cube::fold(&map).unwrap();
+		let topologies: [&dyn Topology; 5] = [&Plain, &cube, &Torus, &Klein, &Clamped];
+		for topology in topologies {
+			let mut walker = Walker::new(&map, &instructions, topology);
+			while walker.tick().is_some() {}
+			prop_assert_eq!(
+				day22::walk(&map, &instructions, topology),
+				walker.password()
+			);
+		}
+	}
+}
+
+#[test]
+fn no_start() {
+	let input = InputSource::Text("  #\n  .\n\n1\n".to_owned());
+	let err = Day22::parse(&input).err().unwrap();
+	let err = err.downcast::<ParseError>().unwrap();
+	assert_eq!(
+		err.diagnostics[0].message,
+		"The top row of the map has no open tile to start from"
+	);
+}
+
+#[test]
+fn not_a_cube() {
+	let (map, _) = net_map("#...\n####\n#...", 2);
+	assert!(Cube::fold(&map).is_ok());
+
+	let (map, _) = net_map("##..\n####", 2);
+	assert!(Cube::fold(&map).is_err());
+	let (map, _) = net_map("######", 2);
+	assert!(Cube::fold(&map).is_err());
+}
+
+/// The last drawings of the example walks from the puzzle statement.
+const EXAMPLE_TRACE_PART1: &str = "        >>v#
+        .#v.
+        #.v.
+        ..v.
+...#...v..v#
+>>>v...>#.>>
+..#v...#....
+...>>>>v..#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+";
+const EXAMPLE_TRACE_PART2: &str = "        >>v#
+        .#v.
+        #.v.
+        ..v.
+...#..^...v#
+.>>>>>^.#.>>
+.^#....#....
+.^........#.
+        ...#..v.
+        .....#v.
+        .#v<<<<.
+        ..v...#.
+";
+
+fn example() -> (Map, Vec<Instruction>) {
+	let input = Path::new(env!("CARGO_MANIFEST_DIR")).join("day22/example.txt");
+	Day22::parse(&input.into()).unwrap()
+}
+
+#[test]
+fn example_trace() {
+	let (map, instructions) = example();
+
+	let trail = day22::trail(&map, &instructions, &Plain);
+	assert_eq!(render::render_net(&map, &trail), EXAMPLE_TRACE_PART1);
+	let cube = Cube::fold(&map).unwrap();
+	let trail = day22::trail(&map, &instructions, &cube);
+	assert_eq!(render::render_net(&map, &trail), EXAMPLE_TRACE_PART2);
+
+	let faces = render::render_faces(&map, &cube, &trail);
+	assert!(faces.starts_with("Face 1 at (8, 0):\n>>v#\n.#v.\n#.v.\n..v.\n\nFace 2"));
+}
+
+fn parse_map(text: &str) -> Map {
+	let (map, _) = Day22::parse(&InputSource::Text(format!("{text}\n\n0\n"))).unwrap();
+	map
+}
+
+/// Take steps until we hit the edge, but no more than `limit` steps.
+fn walk_straight(
+	topology: &dyn Topology,
+	map: &Map,
+	mut pos: Point,
+	mut facing: Facing,
+	limit: usize
+) -> (Point, Facing, usize) {
+	for i in 0 .. limit {
+		match topology.step(map, pos, facing) {
+			Some(next) => (pos, facing) = next,
+			None => return (pos, facing, i)
+		}
+	}
+	(pos, facing, limit)
+}
+
+#[test]
+fn topologies() {
+	let map = parse_map("  ..\n....\n....");
+	let start = Point::new(1, 1);
+
+	// the plain topology skips the void
+	assert_eq!(
+		Plain.step(&map, start, Facing::Up),
+		Some((Point::new(1, 2), Facing::Up))
+	);
+
+	// void tiles are holes on the torus and the Klein bottle
+	assert_eq!(Torus.step(&map, start, Facing::Up), None);
+	assert_eq!(Klein.step(&map, Point::new(1, 1), Facing::Up), None);
+	assert_eq!(
+		walk_straight(&Torus, &map, start, Facing::Left, 8),
+		(start, Facing::Left, 8)
+	);
+	assert_eq!(
+		walk_straight(&Torus, &map, Point::new(3, 0), Facing::Down, 6),
+		(Point::new(3, 0), Facing::Down, 6)
+	);
+
+	// the Klein bottle mirrors the column when crossing the top or bottom edge
+	assert_eq!(
+		walk_straight(&Klein, &map, Point::new(3, 1), Facing::Up, 8),
+		(Point::new(0, 1), Facing::Up, 3)
+	);
+	assert_eq!(
+		walk_straight(&Klein, &map, Point::new(0, 1), Facing::Down, 8),
+		(Point::new(3, 2), Facing::Down, 4)
+	);
+
+	// after crossing the twist, turns are mirrored until crossing back
+	let (open, instructions) =
+		Day22::parse(&InputSource::Text("....\n....\n....\n\nR2R1L3L1L\n".into()))
+			.unwrap();
+	let mut walker = Walker::new(&open, &instructions, &Klein);
+	let mut turns = Vec::new();
+	while let Some(event) = walker.tick() {
+		if let Event::Turned(facing) = event {
+			turns.push((walker.pos(), facing, walker.mirrored()));
+		}
+	}
+	assert_eq!(turns, [
+		(Point::new(0, 0), Facing::Down, false),
+		(Point::new(0, 2), Facing::Left, false),
+		(Point::new(3, 2), Facing::Down, false),
+		(Point::new(0, 2), Facing::Left, true),
+		(Point::new(3, 2), Facing::Up, true)
+	]);
+	assert_eq!(day22::walk(&open, &instructions, &Klein), walker.password());
+
+	// clamping stops at the edge
+	assert_eq!(
+		walk_straight(&Clamped, &map, start, Facing::Right, 8),
+		(Point::new(3, 1), Facing::Right, 2)
+	);
+	assert_eq!(
+		walk_straight(&Clamped, &map, start, Facing::Up, 8),
+		(start, Facing::Up, 0)
+	);
+}
+
+#[test]
+fn debugger() {
+	let (map, instructions) = example();
+	let cube = Cube::fold(&map).unwrap();
+	let mut debugger = Debugger::new(Walker::new(&map, &instructions, &cube));
+	let mut run = |command: &str| {
+		let mut out = Vec::new();
+		assert!(debugger.command(command, &mut out).unwrap());
+		String::from_utf8(out).unwrap()
+	};
+
+	assert_eq!(run("break wall"), "Breakpoint 0: wall\n");
+	assert!(run("continue")
+		.starts_with("Breakpoint 0: hit a wall at (11, 0): at (10, 0) facing >, 1 "));
+	assert!(run("next 2").starts_with("Stopped: at (10, 5) facing v, 3 "));
+	assert!(run("delete").is_empty());
+
+	assert_eq!(run("break wrap"), "Breakpoint 0: wrap\n");
+	assert!(run("continue")
+		.starts_with("Breakpoint 0: moved from (11, 5) to (14, 8): at (14, 8) facing v"));
+	assert_eq!(
+		run("show 1"),
+		"Stopped: at (14, 8) facing v, 5 instructions started (current one unfinished)\n\
+		 \n.v.\n#..\n"
+	);
+	assert!(run("delete 0").is_empty());
+	assert_eq!(run("break face 5"), "Breakpoint 0: face 5\n");
+	assert!(run("continue").starts_with(
+		"Breakpoint 0: moved from (12, 10) to (11, 10): at (11, 10) facing <"
+	));
+	assert!(run("delete 0").is_empty());
+	assert!(run("c").starts_with("Done, the password is 5031: "));
+
+	let mut out = Vec::new();
+	assert!(debugger.command("break region 1 2", &mut out).is_err());
+	assert!(debugger.command("break face 7", &mut out).is_err());
+	assert!(debugger.command("delete -1", &mut out).is_err());
+	assert!(debugger.command("show 1000", &mut out).is_err());
+	assert!(!debugger.command("quit", &mut out).unwrap());
+}
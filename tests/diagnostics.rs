@@ -0,0 +1,109 @@
+use aoc22::{
+	day12::Day12, day13::Day13, day15::Day15, day9::Day9, lines, read, read_partial,
+	InputSource, ParseError, Solution
+};
+use chumsky::{prelude::*, text::digits};
+
+fn parser() -> impl Parser<char, Vec<String>, Error = Simple<char>> {
+	digits(10)
+		.then_ignore(just("\n"))
+		.repeated()
+		.then_ignore(end())
+}
+
+fn parse_err(text: &str) -> ParseError {
+	let input = InputSource::Text(text.to_owned());
+	let err = read(&input, parser()).unwrap_err();
+	err.downcast::<ParseError>().unwrap()
+}
+
+#[test]
+fn position() {
+	let err = parse_err("1\n22\n3x3\n");
+	assert_eq!(err.name, "<text>");
+	assert_eq!(err.diagnostics.len(), 1);
+	let d = &err.diagnostics[0];
+	assert_eq!(d.message, "Unexpected token");
+	assert_eq!(d.span, 6 .. 7);
+	assert_eq!((d.line, d.column), (3, 2));
+	assert_eq!(d.found.as_deref(), Some("x"));
+	assert!(d.expected.contains(&Some("\n".to_owned())));
+}
+
+#[test]
+fn plain() {
+	let err = parse_err("1\nx\n");
+	let plain = err.to_plain();
+	assert!(plain.starts_with("<text>:2:1: error: Unexpected token\n"));
+	assert!(plain.contains("<text>:2:1: note: Unexpected token x\n"));
+}
+
+#[test]
+fn json() {
+	let err = parse_err("1\nx\n");
+	let json: serde_json::Value = serde_json::from_str(&err.to_json()).unwrap();
+	assert_eq!(json["name"], "<text>");
+	assert_eq!(json["diagnostics"][0]["line"], 2);
+	assert_eq!(json["diagnostics"][0]["column"], 1);
+	assert_eq!(json["diagnostics"][0]["found"], "x");
+}
+
+#[test]
+fn partial() {
+	let input = InputSource::Text("1\nx\n3\n4y\n".to_owned());
+	let partial = read_partial(&input, lines(digits(10)).then_ignore(end())).unwrap();
+	assert_eq!(partial.value, Some(vec!["1".to_owned(), "3".to_owned()]));
+	let lines = partial
+		.errors
+		.unwrap()
+		.diagnostics
+		.iter()
+		.map(|d| d.line)
+		.collect::<Vec<_>>();
+	assert_eq!(lines, [2, 4]);
+}
+
+#[test]
+fn multiple_errors() {
+	let input = InputSource::Text(
+		"Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10 y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14 closest beacon is at x=10, y=16
+"
+		.to_owned()
+	);
+	let err = Day15::parse(&input).unwrap_err();
+	let err = err.downcast::<ParseError>().unwrap();
+	let lines = err.diagnostics.iter().map(|d| d.line).collect::<Vec<_>>();
+	assert_eq!(lines, [2, 4]);
+}
+
+#[test]
+fn invalid_direction() {
+	let input = InputSource::Text("R 4\nX 2\nU 3\n".to_owned());
+	let err = Day9::parse(&input).unwrap_err();
+	let err = err.downcast::<ParseError>().unwrap();
+	assert_eq!(err.diagnostics.len(), 1);
+	assert_eq!((err.diagnostics[0].line, err.diagnostics[0].column), (2, 1));
+	assert_eq!(err.diagnostics[0].found.as_deref(), Some("X"));
+}
+
+#[test]
+fn invalid_height() {
+	let input = InputSource::Text("Sab\naEA\n".to_owned());
+	let err = Day12::parse(&input).unwrap_err();
+	let err = err.downcast::<ParseError>().unwrap();
+	assert_eq!(err.diagnostics.len(), 1);
+	assert_eq!((err.diagnostics[0].line, err.diagnostics[0].column), (2, 3));
+	assert_eq!(err.diagnostics[0].found.as_deref(), Some("A"));
+}
+
+#[test]
+fn invalid_packets() {
+	let input = InputSource::Text("[1,x]\n[2]\n\n[3]\n[4]\n\n[5]\n[[6],y]\n".to_owned());
+	let err = Day13::parse(&input).err().unwrap();
+	let err = err.downcast::<ParseError>().unwrap();
+	let lines = err.diagnostics.iter().map(|d| d.line).collect::<Vec<_>>();
+	assert_eq!(lines, [1, 8]);
+}
@@ -0,0 +1,87 @@
+use aoc22::{BoundingBox, Grid, Point};
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct Cell(char);
+
+impl Display for Cell {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self.0 {
+			'\0' => f.write_str("."),
+			ch => write!(f, "{ch}")
+		}
+	}
+}
+
+fn check_backend(mut grid: Grid<Cell>) {
+	assert!(grid.is_empty());
+	assert_eq!(grid.bounds(), None);
+
+	grid.insert(Point::new(2, 1), Cell('a'));
+	grid.insert(Point::new(-1, -2), Cell('b'));
+	assert_eq!(grid.insert(Point::new(2, 1), Cell('c')), Some(Cell('a')));
+	assert_eq!(grid.len(), 2);
+	assert_eq!(
+		grid.bounds(),
+		Some(BoundingBox {
+			min: Point::new(-1, -2),
+			max: Point::new(2, 1)
+		})
+	);
+
+	assert_eq!(grid.get(Point::new(2, 1)), Some(&Cell('c')));
+	assert_eq!(grid.get(Point::new(-1, -2)), Some(&Cell('b')));
+	assert_eq!(grid.get(Point::new(0, 0)), None);
+	assert_eq!(grid.get(Point::new(100, -100)), None);
+	assert_eq!(grid.to_string(), "b...\n....\n....\n...c\n");
+
+	assert_eq!(grid.remove(Point::new(-1, -2)), Some(Cell('b')));
+	assert_eq!(grid.remove(Point::new(-1, -2)), None);
+	assert_eq!(grid.len(), 1);
+	assert_eq!(grid.iter().collect::<Vec<_>>(), vec![(
+		Point::new(2, 1),
+		&Cell('c')
+	)]);
+}
+
+#[test]
+fn dense() {
+	check_backend(Grid::dense());
+}
+
+#[test]
+fn sparse() {
+	check_backend(Grid::sparse());
+}
+
+#[test]
+fn from_rows() {
+	let grid = Grid::from_rows(["ab", "cde"].map(|row| row.chars().map(Cell)));
+	assert_eq!(grid.len(), 5);
+	assert_eq!(grid[Point::new(1, 0)], Cell('b'));
+	assert_eq!(grid[Point::new(2, 1)], Cell('e'));
+	assert_eq!(grid.get(Point::new(2, 0)), None);
+	assert_eq!(grid.to_string(), "ab.\ncde\n");
+}
+
+#[test]
+fn neighbours() {
+	let grid: Grid<u8> = BoundingBox {
+		min: Point::new(0, 0),
+		max: Point::new(2, 2)
+	}
+	.points()
+	.map(|point| (point, (point.y * 3 + point.x) as u8))
+	.collect();
+
+	let mut neighbours4: Vec<_> = grid.neighbours4(Point::new(0, 0)).collect();
+	neighbours4.sort();
+	assert_eq!(neighbours4, vec![
+		(Point::new(0, 1), &3),
+		(Point::new(1, 0), &1)
+	]);
+
+	assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 4);
+	assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+	assert_eq!(grid.neighbours8(Point::new(2, 2)).count(), 3);
+}